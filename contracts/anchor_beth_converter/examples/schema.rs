@@ -1,7 +1,13 @@
 use std::env::current_dir;
 use std::fs::create_dir_all;
 
-use beth::converter::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg};
+use beth::converter::{
+    AllowlistResponse, BackingResponse, ConfigResponse, DenylistResponse, ExecuteMsg,
+    InstantiateMsg, MigrateMsg, NativeSourceResponse, NativeSourcesResponse, PauseStatusResponse,
+    PendingOwnerResponse, QueryMsg, QueuedActionResponse, QueuedActionsResponse, RateLimitResponse,
+    SimulationResponse, SourceTokenResponse, SourceTokensResponse, StatsResponse,
+    TokenMigrationResponse,
+};
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
 fn main() {
@@ -13,5 +19,21 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(PendingOwnerResponse), &out_dir);
+    export_schema(&schema_for!(BackingResponse), &out_dir);
+    export_schema(&schema_for!(SourceTokenResponse), &out_dir);
+    export_schema(&schema_for!(SourceTokensResponse), &out_dir);
+    export_schema(&schema_for!(NativeSourceResponse), &out_dir);
+    export_schema(&schema_for!(NativeSourcesResponse), &out_dir);
+    export_schema(&schema_for!(PauseStatusResponse), &out_dir);
+    export_schema(&schema_for!(RateLimitResponse), &out_dir);
+    export_schema(&schema_for!(SimulationResponse), &out_dir);
+    export_schema(&schema_for!(TokenMigrationResponse), &out_dir);
+    export_schema(&schema_for!(StatsResponse), &out_dir);
+    export_schema(&schema_for!(AllowlistResponse), &out_dir);
+    export_schema(&schema_for!(DenylistResponse), &out_dir);
+    export_schema(&schema_for!(QueuedActionResponse), &out_dir);
+    export_schema(&schema_for!(QueuedActionsResponse), &out_dir);
 }
@@ -0,0 +1,125 @@
+use crate::error::ContractError;
+use crate::state::{
+    add_to_list, is_listed, read_allowlist_only, read_config, read_list, remove_from_list,
+    store_allowlist_only, PREFIX_ALLOWLIST, PREFIX_DENYLIST,
+};
+
+use beth::converter::{AllowlistResponse, DenylistResponse};
+use cosmwasm_std::{Deps, DepsMut, MessageInfo, Response, StdResult};
+
+fn update_list(
+    deps: DepsMut,
+    info: MessageInfo,
+    prefix: &[u8],
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<(), ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    for address in add {
+        let address_raw = deps.api.addr_canonicalize(&address)?;
+        add_to_list(deps.storage, prefix, &address_raw)?;
+    }
+    for address in remove {
+        let address_raw = deps.api.addr_canonicalize(&address)?;
+        remove_from_list(deps.storage, prefix, &address_raw);
+    }
+
+    Ok(())
+}
+
+pub fn update_allowlist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    update_list(deps, info, PREFIX_ALLOWLIST, add, remove)?;
+
+    Ok(Response::new().add_attribute("action", "update_allowlist"))
+}
+
+pub fn update_denylist(
+    deps: DepsMut,
+    info: MessageInfo,
+    add: Vec<String>,
+    remove: Vec<String>,
+) -> Result<Response, ContractError> {
+    update_list(deps, info, PREFIX_DENYLIST, add, remove)?;
+
+    Ok(Response::new().add_attribute("action", "update_denylist"))
+}
+
+pub fn set_allowlist_only(
+    deps: DepsMut,
+    info: MessageInfo,
+    allowlist_only: bool,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    store_allowlist_only(deps.storage, allowlist_only)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_allowlist_only"),
+        ("allowlist_only", &allowlist_only.to_string()),
+    ]))
+}
+
+/// Denylisted senders can never convert, and only allowlisted ones can
+/// in allowlist-only mode
+pub(crate) fn assert_sender_allowed(deps: Deps, sender: &str) -> Result<(), ContractError> {
+    let sender_raw = deps.api.addr_canonicalize(sender)?;
+    if is_listed(deps.storage, PREFIX_DENYLIST, &sender_raw)? {
+        return Err(ContractError::SenderDenied {
+            sender: sender.to_string(),
+        });
+    }
+    if read_allowlist_only(deps.storage)?
+        && !is_listed(deps.storage, PREFIX_ALLOWLIST, &sender_raw)?
+    {
+        return Err(ContractError::SenderNotAllowed {
+            sender: sender.to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+pub fn query_allowlist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<AllowlistResponse> {
+    let start_after = match start_after {
+        Some(address) => Some(deps.api.addr_canonicalize(&address)?),
+        None => None,
+    };
+
+    Ok(AllowlistResponse {
+        allowlist_only: read_allowlist_only(deps.storage)?,
+        addresses: read_list(deps, PREFIX_ALLOWLIST, start_after, limit)?,
+    })
+}
+
+pub fn query_denylist(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<DenylistResponse> {
+    let start_after = match start_after {
+        Some(address) => Some(deps.api.addr_canonicalize(&address)?),
+        None => None,
+    };
+
+    Ok(DenylistResponse {
+        addresses: read_list(deps, PREFIX_DENYLIST, start_after, limit)?,
+    })
+}
@@ -0,0 +1,115 @@
+use crate::convert::Source;
+use crate::error::ContractError;
+use crate::math::normalize_decimals;
+use crate::querier::{query_token_balance, query_total_supply};
+use crate::state::{read_all_native_sources, read_all_source_tokens, read_config, SourceToken};
+
+use beth::converter::BackingResponse;
+use cosmwasm_std::{Deps, Env, Uint128};
+
+/// Source token and native balances held by the converter and anchor token supply,
+/// both normalized to the largest decimals of all tokens
+pub(crate) struct Backing {
+    pub decimals: u8,
+    pub wormhole_balance: Uint128,
+    pub anchor_supply: Uint128,
+}
+
+impl Backing {
+    pub fn is_backed(&self) -> bool {
+        self.wormhole_balance >= self.anchor_supply
+    }
+}
+
+pub(crate) fn compute_backing(deps: Deps, env: &Env) -> Result<Backing, ContractError> {
+    let config = read_config(deps.storage)?;
+    if config.anchor_token_address.is_none() || config.anchor_decimals.is_none() {
+        return Err(ContractError::TokensNotRegistered {});
+    }
+    let anchor_decimals = config
+        .anchor_decimals
+        .ok_or(ContractError::TokensNotRegistered {})?;
+
+    let source_tokens = read_all_source_tokens(deps.storage)?;
+    let native_sources = read_all_native_sources(deps.storage)?;
+    let decimals = source_tokens
+        .iter()
+        .map(|(_, source)| source.decimals)
+        .chain(native_sources.iter().map(|(_, source)| source.decimals))
+        .fold(anchor_decimals, u8::max);
+
+    let mut wormhole_balance = Uint128::zero();
+    for (token_address, source) in source_tokens {
+        let balance = query_token_balance(
+            deps,
+            deps.api.addr_humanize(&token_address)?,
+            env.contract.address.clone(),
+        )?;
+        wormhole_balance += normalize_decimals(balance, source.decimals, decimals);
+    }
+    for (denom, source) in native_sources {
+        let balance = deps
+            .querier
+            .query_balance(env.contract.address.clone(), denom)?
+            .amount;
+        wormhole_balance += normalize_decimals(balance, source.decimals, decimals);
+    }
+
+    let anchor_supply = query_total_supply(
+        deps,
+        deps.api.addr_humanize(
+            &config
+                .anchor_token_address
+                .ok_or(ContractError::TokensNotRegistered {})?,
+        )?,
+    )?;
+
+    Ok(Backing {
+        decimals,
+        wormhole_balance,
+        anchor_supply: normalize_decimals(anchor_supply, anchor_decimals, decimals),
+    })
+}
+
+/// Refuse a redemption from a source which no longer holds what has been deposited
+/// through it. Only the redeemed source is queried, so a redemption costs the same
+/// however many sources are registered; the full solvency is reported by `query_backing`
+pub(crate) fn assert_source_backed(
+    deps: Deps,
+    env: &Env,
+    source_token: &Source,
+    source: &SourceToken,
+) -> Result<(), ContractError> {
+    let balance = match source_token {
+        Source::Token(token_address) => query_token_balance(
+            deps,
+            deps.api.addr_humanize(token_address)?,
+            env.contract.address.clone(),
+        )?,
+        Source::Native(denom) => {
+            deps.querier
+                .query_balance(env.contract.address.clone(), denom)?
+                .amount
+        }
+    };
+
+    if balance < source.backing {
+        return Err(ContractError::Undercollateralized {});
+    }
+
+    Ok(())
+}
+
+pub fn query_backing(deps: Deps, env: Env) -> Result<BackingResponse, ContractError> {
+    let backing = compute_backing(deps, &env)?;
+
+    Ok(BackingResponse {
+        decimals: backing.decimals,
+        wormhole_balance: backing.wormhole_balance,
+        anchor_supply: backing.anchor_supply,
+        is_backed: backing.is_backed(),
+        surplus: backing
+            .wormhole_balance
+            .saturating_sub(backing.anchor_supply),
+    })
+}
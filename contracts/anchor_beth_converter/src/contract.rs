@@ -1,19 +1,53 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use crate::state::{read_config, store_config, Config};
+use crate::access::{
+    assert_sender_allowed, query_allowlist, query_denylist, set_allowlist_only, update_allowlist,
+    update_denylist,
+};
+use crate::error::ContractError;
+use crate::state::{
+    read_config, read_native_source, read_native_sources, read_pause_status, read_source_token,
+    read_source_tokens, read_timelock, remove_native_source, remove_source_token, store_config,
+    store_native_source, store_pause_status, store_source_token, Config, SourceToken,
+};
 
 use beth::converter::{
-    ConfigResponse, Cw20HookMsg, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg,
+    ConfigResponse, ConvertDirection, Cw20HookMsg, ExecuteMsg, Forward, InstantiateMsg, MigrateMsg,
+    NativeSourceResponse, NativeSourcesResponse, PauseStatusResponse, QueryMsg,
+    SourceTokenResponse, SourceTokensResponse,
 };
 use cosmwasm_std::{
-    from_binary, to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, StdError,
-    StdResult, Uint128, WasmMsg,
+    from_binary, to_binary, Binary, CanonicalAddr, Deps, DepsMut, Env, MessageInfo, Response,
+    StdResult, Uint128,
 };
 
-use crate::math::{convert_to_anchor_decimals, convert_to_wormhole_decimals};
+use crate::backing::query_backing;
+use crate::convert::{
+    execute_convert_to_anchor, execute_convert_to_wormhole, query_simulate_convert_to_anchor,
+    query_simulate_convert_to_wormhole, AnchorFunds, Destination, Source, SourceFunds,
+};
+use crate::migration::{migrate_contract, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::ownership::{
+    accept_ownership, cancel_ownership_transfer, propose_new_owner, query_pending_owner,
+};
 use crate::querier::query_decimals;
-use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
+use crate::rate_limit::{query_rate_limit, update_rate_limit};
+use crate::recovery::{recover_token, sweep_surplus};
+use crate::stats::query_stats;
+use crate::timelock::{
+    cancel_action, execute_action, is_timelocked, propose_action, query_queued_action,
+    query_queued_actions, set_timelock_delay,
+};
+use crate::token_migration::{
+    cancel_token_migration, execute_token_migration, finalize_token_migration,
+    propose_token_migration, query_token_migration,
+};
+use cw2::set_contract_version;
+use cw20::Cw20ReceiveMsg;
+
+// conversion fees cannot be set above 1%
+const MAX_FEE_BPS: u64 = 100;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
 pub fn instantiate(
@@ -21,28 +55,128 @@ pub fn instantiate(
     _env: Env,
     _info: MessageInfo,
     msg: InstantiateMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     // cannot register the token at the inistantiation
     // because for the anchor token contract, converter needs to be minter.
     let conf = Config {
         owner: deps.api.addr_canonicalize(&msg.owner)?,
         anchor_token_address: None,
         wormhole_token_address: None,
+        anchor_decimals: None,
+        fee_collector: None,
+        to_anchor_fee_bps: 0,
+        to_wormhole_fee_bps: 0,
+        guardian: None,
     };
 
     store_config(deps.storage).save(&conf)?;
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
 
     Ok(Response::default())
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn execute(deps: DepsMut, env: Env, info: MessageInfo, msg: ExecuteMsg) -> StdResult<Response> {
+pub fn execute(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    // once a delay is set, owner operations only run through the timelock
+    if is_timelocked(&msg) && read_timelock(deps.storage)?.delay > 0 {
+        return Err(ContractError::MustBeQueued {});
+    }
+
+    dispatch(deps, env, info, msg)
+}
+
+pub(crate) fn dispatch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
     match msg {
         ExecuteMsg::Receive(msg) => receive_cw20(deps, env, info, msg),
         ExecuteMsg::RegisterTokens {
             anchor_token_address,
             wormhole_token_address,
         } => register_tokens(deps, info, anchor_token_address, wormhole_token_address),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => propose_new_owner(deps, env, info, new_owner, expires_in),
+        ExecuteMsg::CancelOwnershipTransfer {} => cancel_ownership_transfer(deps, info),
+        ExecuteMsg::AcceptOwnership {} => accept_ownership(deps, env, info),
+        ExecuteMsg::AddSourceToken { token_address } => add_source_token(deps, info, token_address),
+        ExecuteMsg::UpdateSourceToken {
+            token_address,
+            enabled,
+        } => update_source_token(deps, info, token_address, enabled),
+        ExecuteMsg::RemoveSourceToken { token_address } => remove_source(deps, info, token_address),
+        ExecuteMsg::AddNativeSource { denom, decimals } => {
+            add_native_source(deps, info, denom, decimals)
+        }
+        ExecuteMsg::UpdateNativeSource { denom, enabled } => {
+            update_native_source(deps, info, denom, enabled)
+        }
+        ExecuteMsg::RemoveNativeSource { denom } => remove_native(deps, info, denom),
+        ExecuteMsg::ConvertNativeToAnchor { recipient, forward } => {
+            convert_native_to_anchor(deps, env, info, recipient, forward)
+        }
+        ExecuteMsg::ConvertFrom {
+            direction,
+            amount,
+            recipient,
+        } => convert_from(deps, env, info, direction, amount, recipient),
+        ExecuteMsg::UpdateFees {
+            fee_collector,
+            to_anchor_fee_bps,
+            to_wormhole_fee_bps,
+        } => update_fees(
+            deps,
+            info,
+            fee_collector,
+            to_anchor_fee_bps,
+            to_wormhole_fee_bps,
+        ),
+        ExecuteMsg::SetGuardian { guardian } => set_guardian(deps, info, guardian),
+        ExecuteMsg::Pause { mint, redeem } => pause(deps, info, mint, redeem),
+        ExecuteMsg::Unpause { mint, redeem } => unpause(deps, info, mint, redeem),
+        ExecuteMsg::UpdateAllowlist { add, remove } => update_allowlist(deps, info, add, remove),
+        ExecuteMsg::UpdateDenylist { add, remove } => update_denylist(deps, info, add, remove),
+        ExecuteMsg::SetAllowlistOnly { allowlist_only } => {
+            set_allowlist_only(deps, info, allowlist_only)
+        }
+        ExecuteMsg::UpdateRateLimit {
+            window,
+            mint_limit,
+            redeem_limit,
+        } => update_rate_limit(deps, env, info, window, mint_limit, redeem_limit),
+        ExecuteMsg::SweepSurplus { recipient } => sweep_surplus(deps, env, info, recipient),
+        ExecuteMsg::RecoverToken {
+            token_address,
+            recipient,
+        } => recover_token(deps, env, info, token_address, recipient),
+        ExecuteMsg::ProposeTokenMigration {
+            new_token_address,
+            migration_contract,
+            migration_msg,
+        } => propose_token_migration(
+            deps,
+            env,
+            info,
+            new_token_address,
+            migration_contract,
+            migration_msg,
+        ),
+        ExecuteMsg::ProposeAction { msg } => propose_action(deps, env, info, *msg),
+        ExecuteMsg::CancelAction { id } => cancel_action(deps, info, id),
+        ExecuteMsg::SetTimelockDelay { delay } => set_timelock_delay(deps, info, delay),
+        ExecuteMsg::ExecuteAction { id } => execute_action(deps, env, id),
+        ExecuteMsg::CancelTokenMigration {} => cancel_token_migration(deps, info),
+        ExecuteMsg::ExecuteTokenMigration {} => execute_token_migration(deps, env, info),
+        ExecuteMsg::FinalizeTokenMigration {} => finalize_token_migration(deps, env, info),
     }
 }
 
@@ -52,31 +186,84 @@ pub fn receive_cw20(
     env: Env,
     info: MessageInfo,
     cw20_msg: Cw20ReceiveMsg,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let contract_addr = info.sender.clone();
+    assert_sender_allowed(deps.as_ref(), &cw20_msg.sender)?;
 
     match from_binary(&cw20_msg.msg) {
-        Ok(Cw20HookMsg::ConvertWormholeToAnchor {}) => {
-            // only wormhole beth token contract can execute this message
+        Ok(Cw20HookMsg::ConvertWormholeToAnchor { recipient, forward }) => {
+            // only registered source token contracts can execute this message
+            if read_source_token(
+                deps.storage,
+                &deps.api.addr_canonicalize(contract_addr.as_str())?,
+            )?
+            .is_none()
+            {
+                return Err(ContractError::Unauthorized {});
+            }
+            let source_token = Source::Token(deps.api.addr_canonicalize(contract_addr.as_str())?);
+            let funds = SourceFunds::Received {
+                sender: cw20_msg.sender.clone(),
+            };
+            let destination = Destination::new(deps.api, cw20_msg.sender, recipient, forward)?;
+            execute_convert_to_anchor(deps, env, source_token, cw20_msg.amount, funds, destination)
+        }
+        Ok(Cw20HookMsg::ConvertAnchorToWormhole {
+            source_token,
+            recipient,
+            forward,
+        }) => {
+            // only anchor beth token contract can execute this message
             let conf = read_config(deps.storage)?;
             if deps.api.addr_canonicalize(contract_addr.as_str())?
-                != conf.wormhole_token_address.unwrap()
+                != conf
+                    .anchor_token_address
+                    .ok_or(ContractError::TokensNotRegistered {})?
             {
-                return Err(StdError::generic_err("unauthorized"));
+                return Err(ContractError::Unauthorized {});
             }
-            execute_convert_to_anchor(deps, env, info, cw20_msg.amount, cw20_msg.sender)
+            let source_token = Source::resolve(deps.as_ref(), source_token)?;
+            let destination =
+                Destination::new(deps.api, cw20_msg.sender.clone(), recipient, forward)?;
+            execute_convert_to_wormhole(
+                deps,
+                env,
+                cw20_msg.amount,
+                AnchorFunds::Received {
+                    sender: cw20_msg.sender,
+                },
+                source_token,
+                destination,
+            )
         }
-        Ok(Cw20HookMsg::ConvertAnchorToWormhole {}) => {
+        Ok(Cw20HookMsg::ConvertAnchorToNative {
+            denom,
+            recipient,
+            forward,
+        }) => {
             // only anchor beth token contract can execute this message
             let conf = read_config(deps.storage)?;
             if deps.api.addr_canonicalize(contract_addr.as_str())?
-                != conf.anchor_token_address.unwrap()
+                != conf
+                    .anchor_token_address
+                    .ok_or(ContractError::TokensNotRegistered {})?
             {
-                return Err(StdError::generic_err("unauthorized"));
+                return Err(ContractError::Unauthorized {});
             }
-            execute_convert_to_wormhole(deps, env, info, cw20_msg.amount, cw20_msg.sender)
+            let destination =
+                Destination::new(deps.api, cw20_msg.sender.clone(), recipient, forward)?;
+            execute_convert_to_wormhole(
+                deps,
+                env,
+                cw20_msg.amount,
+                AnchorFunds::Received {
+                    sender: cw20_msg.sender,
+                },
+                Source::Native(denom),
+                destination,
+            )
         }
-        Err(err) => Err(err),
+        Err(_) => Err(ContractError::UnknownHook {}),
     }
 }
 
@@ -85,21 +272,45 @@ pub fn register_tokens(
     info: MessageInfo,
     anchor_token_address: String,
     wormhole_token_address: String,
-) -> StdResult<Response> {
+) -> Result<Response, ContractError> {
     let mut config = read_config(deps.storage)?;
 
     if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
-        return Err(StdError::generic_err("unauthorized"));
+        return Err(ContractError::Unauthorized {});
     }
 
     // if the token contract is  already register we cannot change the address
     if config.anchor_token_address.is_none() {
         config.anchor_token_address = Some(deps.api.addr_canonicalize(&anchor_token_address)?);
+        // decimals never change, so they are queried once here instead of on every conversion
+        config.anchor_decimals = Some(query_decimals(
+            deps.as_ref(),
+            deps.api.addr_validate(&anchor_token_address)?,
+        )?);
     }
 
     // if the token contract is  already register we cannot change the address
     if config.wormhole_token_address.is_none() {
-        config.wormhole_token_address = Some(deps.api.addr_canonicalize(&wormhole_token_address)?);
+        let wormhole_token_raw = deps.api.addr_canonicalize(&wormhole_token_address)?;
+
+        // the wormhole token is the first whitelisted source token
+        if read_source_token(deps.storage, &wormhole_token_raw)?.is_none() {
+            let decimals = query_decimals(
+                deps.as_ref(),
+                deps.api.addr_validate(&wormhole_token_address)?,
+            )?;
+            store_source_token(
+                deps.storage,
+                &wormhole_token_raw,
+                &SourceToken {
+                    decimals,
+                    enabled: true,
+                    backing: Uint128::zero(),
+                },
+            )?;
+        }
+
+        config.wormhole_token_address = Some(wormhole_token_raw);
     }
 
     store_config(deps.storage).save(&config)?;
@@ -107,153 +318,536 @@ pub fn register_tokens(
     Ok(Response::new().add_attributes(vec![("action", "register_token_contracts")]))
 }
 
-pub(crate) fn execute_convert_to_anchor(
+pub fn add_source_token(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
-    amount: Uint128,
-    sender: String,
-) -> StdResult<Response> {
+    info: MessageInfo,
+    token_address: String,
+) -> Result<Response, ContractError> {
     let config = read_config(deps.storage)?;
 
-    if config.wormhole_token_address.is_none() || config.wormhole_token_address.is_none() {
-        return Err(StdError::generic_err(
-            "wormhole or anchor token must be registered first",
-        ));
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token_addr = deps.api.addr_validate(&token_address)?;
+    let token_addr_raw = deps.api.addr_canonicalize(token_addr.as_str())?;
+    if read_source_token(deps.storage, &token_addr_raw)?.is_some() {
+        return Err(ContractError::SourceAlreadyRegistered {
+            token: token_addr.to_string(),
+        });
     }
 
-    let wormhole_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.wormhole_token_address.as_ref().unwrap())
-            .unwrap(),
+    let decimals = query_decimals(deps.as_ref(), token_addr.clone())?;
+    store_source_token(
+        deps.storage,
+        &token_addr_raw,
+        &SourceToken {
+            decimals,
+            enabled: true,
+            backing: Uint128::zero(),
+        },
     )?;
 
-    let anchor_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.anchor_token_address.as_ref().unwrap())
-            .unwrap(),
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_source_token"),
+        ("source_token", token_addr.as_str()),
+        ("decimals", &decimals.to_string()),
+    ]))
+}
+
+pub fn update_source_token(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_address: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token_addr_raw = deps.api.addr_canonicalize(&token_address)?;
+    let mut source_token = load_source_token(deps.as_ref(), &token_addr_raw)?;
+    source_token.enabled = enabled;
+    store_source_token(deps.storage, &token_addr_raw, &source_token)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_source_token"),
+        ("source_token", &token_address),
+        ("enabled", &enabled.to_string()),
+    ]))
+}
+
+pub fn remove_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    token_address: String,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token_addr_raw = deps.api.addr_canonicalize(&token_address)?;
+    if config.wormhole_token_address == Some(token_addr_raw.clone()) {
+        return Err(ContractError::WormholeTokenRemoval {});
+    }
+
+    // anchor token holders must always be able to redeem their deposits
+    let source_token = load_source_token(deps.as_ref(), &token_addr_raw)?;
+    if !source_token.backing.is_zero() {
+        return Err(ContractError::SourceStillBacking {
+            backing: source_token.backing,
+        });
+    }
+
+    remove_source_token(deps.storage, &token_addr_raw);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "remove_source_token"),
+        ("source_token", &token_address),
+    ]))
+}
+
+pub fn add_native_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    decimals: u8,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if read_native_source(deps.storage, &denom)?.is_some() {
+        return Err(ContractError::SourceAlreadyRegistered {
+            token: denom.to_string(),
+        });
+    }
+
+    // bank denoms have no token info to query, so the owner provides the decimals
+    store_native_source(
+        deps.storage,
+        &denom,
+        &SourceToken {
+            decimals,
+            enabled: true,
+            backing: Uint128::zero(),
+        },
     )?;
 
-    // should convert to anchor decimals
-    let mint_amount = convert_to_anchor_decimals(amount, anchor_decimals, wormhole_decimals)?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "add_native_source"),
+        ("denom", &denom),
+        ("decimals", &decimals.to_string()),
+    ]))
+}
 
-    Ok(Response::new()
-        .add_message(CosmosMsg::Wasm(WasmMsg::Execute {
-            contract_addr: deps
-                .api
-                .addr_humanize(&config.anchor_token_address.unwrap())?
-                .to_string(),
-            msg: to_binary(&Cw20ExecuteMsg::Mint {
-                recipient: sender.to_string(),
-                amount: mint_amount,
-            })?,
-            funds: vec![],
-        }))
-        .add_attributes(vec![
-            ("action", "convert-to-anchor"),
-            ("recipient", &sender),
-            ("minted_amount", &mint_amount.to_string()),
-        ]))
+pub fn update_native_source(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+    enabled: bool,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let source = Source::Native(denom.clone());
+    let mut native_source = source.load(deps.as_ref())?;
+    native_source.enabled = enabled;
+    source.store(deps.storage, &native_source)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_native_source"),
+        ("denom", &denom),
+        ("enabled", &enabled.to_string()),
+    ]))
 }
 
-pub(crate) fn execute_convert_to_wormhole(
+pub fn remove_native(
     deps: DepsMut,
-    _env: Env,
-    _info: MessageInfo,
+    info: MessageInfo,
+    denom: String,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // anchor token holders must always be able to redeem their deposits
+    let native_source = Source::Native(denom.clone()).load(deps.as_ref())?;
+    if !native_source.backing.is_zero() {
+        return Err(ContractError::SourceStillBacking {
+            backing: native_source.backing,
+        });
+    }
+
+    remove_native_source(deps.storage, &denom);
+
+    Ok(Response::new().add_attributes(vec![("action", "remove_native_source"), ("denom", &denom)]))
+}
+
+pub fn convert_native_to_anchor(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Option<String>,
+    forward: Option<Forward>,
+) -> Result<Response, ContractError> {
+    if info.funds.len() != 1 {
+        return Err(ContractError::InvalidNativeFunds {});
+    }
+    let coin = &info.funds[0];
+    if coin.amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    assert_sender_allowed(deps.as_ref(), info.sender.as_str())?;
+
+    let destination = Destination::new(deps.api, info.sender.to_string(), recipient, forward)?;
+    execute_convert_to_anchor(
+        deps,
+        env,
+        Source::Native(coin.denom.clone()),
+        coin.amount,
+        SourceFunds::Received {
+            sender: info.sender.to_string(),
+        },
+        destination,
+    )
+}
+
+pub fn convert_from(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    direction: ConvertDirection,
     amount: Uint128,
-    sender: String,
-) -> StdResult<Response> {
+    recipient: Option<String>,
+) -> Result<Response, ContractError> {
+    if amount.is_zero() {
+        return Err(ContractError::ZeroAmount {});
+    }
+    assert_sender_allowed(deps.as_ref(), info.sender.as_str())?;
+
+    let owner = info.sender.to_string();
+    let destination = Destination::new(deps.api, owner.clone(), recipient, None)?;
+    match direction {
+        ConvertDirection::ToAnchor { source_token } => {
+            let source_token = Source::resolve(deps.as_ref(), source_token)?;
+            execute_convert_to_anchor(
+                deps,
+                env,
+                source_token,
+                amount,
+                SourceFunds::Allowance { owner },
+                destination,
+            )
+        }
+        ConvertDirection::ToWormhole { source_token } => {
+            let source_token = Source::resolve(deps.as_ref(), source_token)?;
+            execute_convert_to_wormhole(
+                deps,
+                env,
+                amount,
+                AnchorFunds::Allowance { owner },
+                source_token,
+                destination,
+            )
+        }
+    }
+}
+
+pub fn update_fees(
+    deps: DepsMut,
+    info: MessageInfo,
+    fee_collector: Option<String>,
+    to_anchor_fee_bps: Option<u64>,
+    to_wormhole_fee_bps: Option<u64>,
+) -> Result<Response, ContractError> {
+    let mut config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(fee_collector) = fee_collector {
+        config.fee_collector = Some(deps.api.addr_canonicalize(&fee_collector)?);
+    }
+
+    if let Some(to_anchor_fee_bps) = to_anchor_fee_bps {
+        config.to_anchor_fee_bps = to_anchor_fee_bps;
+    }
+
+    if let Some(to_wormhole_fee_bps) = to_wormhole_fee_bps {
+        config.to_wormhole_fee_bps = to_wormhole_fee_bps;
+    }
+
+    if config.to_anchor_fee_bps > MAX_FEE_BPS || config.to_wormhole_fee_bps > MAX_FEE_BPS {
+        return Err(ContractError::FeeTooHigh {
+            max_bps: MAX_FEE_BPS,
+        });
+    }
+
+    if config.fee_collector.is_none()
+        && (config.to_anchor_fee_bps != 0 || config.to_wormhole_fee_bps != 0)
+    {
+        return Err(ContractError::FeeCollectorNotSet {});
+    }
+
+    store_config(deps.storage).save(&config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_fees"),
+        ("to_anchor_fee_bps", &config.to_anchor_fee_bps.to_string()),
+        (
+            "to_wormhole_fee_bps",
+            &config.to_wormhole_fee_bps.to_string(),
+        ),
+    ]))
+}
+
+pub fn set_guardian(
+    deps: DepsMut,
+    info: MessageInfo,
+    guardian: Option<String>,
+) -> Result<Response, ContractError> {
+    let mut config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    config.guardian = match &guardian {
+        Some(guardian) => Some(deps.api.addr_canonicalize(guardian)?),
+        None => None,
+    };
+    store_config(deps.storage).save(&config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_guardian"),
+        ("guardian", &guardian.unwrap_or_default()),
+    ]))
+}
+
+pub fn pause(
+    deps: DepsMut,
+    info: MessageInfo,
+    mint: bool,
+    redeem: bool,
+) -> Result<Response, ContractError> {
     let config = read_config(deps.storage)?;
-    if config.wormhole_token_address.is_none() || config.wormhole_token_address.is_none() {
-        return Err(StdError::generic_err(
-            "wormhole or anchor token must be registered first",
-        ));
+    let sender_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+
+    // both the owner and the guardian can pause
+    if config.owner != sender_raw && config.guardian != Some(sender_raw) {
+        return Err(ContractError::Unauthorized {});
     }
 
-    let wormhole_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.wormhole_token_address.as_ref().unwrap())
-            .unwrap(),
-    )?;
+    let mut pause_status = read_pause_status(deps.storage)?;
+    pause_status.mint_paused |= mint;
+    pause_status.redeem_paused |= redeem;
+    store_pause_status(deps.storage, &pause_status)?;
 
-    let anchor_decimals = query_decimals(
-        deps.as_ref(),
-        deps.api
-            .addr_humanize(config.anchor_token_address.as_ref().unwrap())
-            .unwrap(),
-    )?;
+    Ok(Response::new().add_attributes(vec![
+        ("action", "pause"),
+        ("mint_paused", &pause_status.mint_paused.to_string()),
+        ("redeem_paused", &pause_status.redeem_paused.to_string()),
+    ]))
+}
 
-    // should convert to wormhole decimals
-    let return_amount = convert_to_wormhole_decimals(amount, anchor_decimals, wormhole_decimals)?;
-
-    Ok(Response::new()
-        .add_messages(vec![
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: deps
-                    .api
-                    .addr_humanize(&config.wormhole_token_address.unwrap())?
-                    .to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Transfer {
-                    recipient: sender.clone(),
-                    amount: return_amount,
-                })?,
-                funds: vec![],
-            }),
-            CosmosMsg::Wasm(WasmMsg::Execute {
-                contract_addr: deps
-                    .api
-                    .addr_humanize(&config.anchor_token_address.unwrap())?
-                    .to_string(),
-                msg: to_binary(&Cw20ExecuteMsg::Burn { amount })?,
-                funds: vec![],
-            }),
-        ])
-        .add_attributes(vec![
-            ("action", "convert-to-wormhole"),
-            ("recipient", &sender),
-            ("return_amount", &return_amount.to_string()),
-            ("burn_amount", &amount.to_string()),
-        ]))
+pub fn unpause(
+    deps: DepsMut,
+    info: MessageInfo,
+    mint: bool,
+    redeem: bool,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    // only the owner can unpause
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut pause_status = read_pause_status(deps.storage)?;
+    pause_status.mint_paused &= !mint;
+    pause_status.redeem_paused &= !redeem;
+    store_pause_status(deps.storage, &pause_status)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "unpause"),
+        ("mint_paused", &pause_status.mint_paused.to_string()),
+        ("redeem_paused", &pause_status.redeem_paused.to_string()),
+    ]))
+}
+
+pub(crate) fn load_source_token(
+    deps: Deps,
+    token_address: &CanonicalAddr,
+) -> Result<SourceToken, ContractError> {
+    read_source_token(deps.storage, token_address)?.ok_or_else(|| {
+        ContractError::SourceNotRegistered {
+            token: deps
+                .api
+                .addr_humanize(token_address)
+                .map(|addr| addr.to_string())
+                .unwrap_or_default(),
+        }
+    })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
-    match msg {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> Result<Binary, ContractError> {
+    let res = match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
-    }
+        QueryMsg::SourceToken { token_address } => {
+            to_binary(&query_source_token(deps, token_address)?)
+        }
+        QueryMsg::SourceTokens { start_after, limit } => {
+            to_binary(&query_source_tokens(deps, start_after, limit)?)
+        }
+        QueryMsg::NativeSource { denom } => to_binary(&query_native_source(deps, denom)?),
+        QueryMsg::NativeSources { start_after, limit } => {
+            to_binary(&query_native_sources(deps, start_after, limit)?)
+        }
+        QueryMsg::PauseStatus {} => to_binary(&query_pause_status(deps)?),
+        QueryMsg::RateLimit {} => to_binary(&query_rate_limit(deps.storage, &env.block)?),
+        QueryMsg::PendingOwner {} => to_binary(&query_pending_owner(deps)?),
+        QueryMsg::Backing {} => to_binary(&query_backing(deps, env)?),
+        QueryMsg::TokenMigration {} => to_binary(&query_token_migration(deps)?),
+        QueryMsg::Stats {} => to_binary(&query_stats(deps)?),
+        QueryMsg::QueuedAction { id } => to_binary(&query_queued_action(deps, id)?),
+        QueryMsg::QueuedActions { start_after, limit } => {
+            to_binary(&query_queued_actions(deps, start_after, limit)?)
+        }
+        QueryMsg::Allowlist { start_after, limit } => {
+            to_binary(&query_allowlist(deps, start_after, limit)?)
+        }
+        QueryMsg::Denylist { start_after, limit } => {
+            to_binary(&query_denylist(deps, start_after, limit)?)
+        }
+        QueryMsg::SimulateConvertToAnchor {
+            source_token,
+            amount,
+        } => to_binary(&query_simulate_convert_to_anchor(
+            deps,
+            env,
+            source_token,
+            amount,
+        )?),
+        QueryMsg::SimulateConvertToWormhole {
+            source_token,
+            amount,
+        } => to_binary(&query_simulate_convert_to_wormhole(
+            deps,
+            env,
+            source_token,
+            amount,
+        )?),
+    };
+    Ok(res?)
 }
 
 fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config: Config = read_config(deps.storage)?;
-    let anchor_token = if config.anchor_token_address.is_some() {
-        Some(
-            deps.api
-                .addr_humanize(&config.anchor_token_address.unwrap())?
-                .to_string(),
-        )
-    } else {
-        None
+    let anchor_token = match config.anchor_token_address {
+        Some(anchor_token) => Some(deps.api.addr_humanize(&anchor_token)?.to_string()),
+        None => None,
     };
-    let wormhole_token = if config.wormhole_token_address.is_some() {
-        Some(
-            deps.api
-                .addr_humanize(&config.wormhole_token_address.unwrap())?
-                .to_string(),
-        )
-    } else {
-        None
+    let (wormhole_token, wormhole_decimals) = match config.wormhole_token_address {
+        Some(wormhole_token) => (
+            Some(deps.api.addr_humanize(&wormhole_token)?.to_string()),
+            read_source_token(deps.storage, &wormhole_token)?.map(|source| source.decimals),
+        ),
+        None => (None, None),
+    };
+    let fee_collector = match config.fee_collector {
+        Some(fee_collector) => Some(deps.api.addr_humanize(&fee_collector)?.to_string()),
+        None => None,
     };
     Ok(ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?.to_string(),
         anchor_token_address: anchor_token,
         wormhole_token_address: wormhole_token,
+        anchor_decimals: config.anchor_decimals,
+        wormhole_decimals,
+        fee_collector,
+        to_anchor_fee_bps: config.to_anchor_fee_bps,
+        to_wormhole_fee_bps: config.to_wormhole_fee_bps,
+    })
+}
+
+fn query_source_token(
+    deps: Deps,
+    token_address: String,
+) -> Result<SourceTokenResponse, ContractError> {
+    let source_token = load_source_token(deps, &deps.api.addr_canonicalize(&token_address)?)?;
+    Ok(SourceTokenResponse {
+        token_address,
+        decimals: source_token.decimals,
+        enabled: source_token.enabled,
+        backing: source_token.backing,
+    })
+}
+
+fn query_source_tokens(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<SourceTokensResponse> {
+    let start_after = if let Some(start_after) = start_after {
+        Some(deps.api.addr_canonicalize(&start_after)?)
+    } else {
+        None
+    };
+
+    let source_tokens = read_source_tokens(deps, start_after, limit)?;
+
+    Ok(SourceTokensResponse { source_tokens })
+}
+
+fn query_native_source(deps: Deps, denom: String) -> Result<NativeSourceResponse, ContractError> {
+    let native_source = Source::Native(denom.clone()).load(deps)?;
+    Ok(NativeSourceResponse {
+        denom,
+        decimals: native_source.decimals,
+        enabled: native_source.enabled,
+        backing: native_source.backing,
+    })
+}
+
+fn query_native_sources(
+    deps: Deps,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<NativeSourcesResponse> {
+    let native_sources = read_native_sources(deps.storage, start_after, limit)?;
+
+    Ok(NativeSourcesResponse { native_sources })
+}
+
+fn query_pause_status(deps: Deps) -> StdResult<PauseStatusResponse> {
+    let config = read_config(deps.storage)?;
+    let pause_status = read_pause_status(deps.storage)?;
+    let guardian = match config.guardian {
+        Some(guardian) => Some(deps.api.addr_humanize(&guardian)?.to_string()),
+        None => None,
+    };
+    Ok(PauseStatusResponse {
+        guardian,
+        mint_paused: pause_status.mint_paused,
+        redeem_paused: pause_status.redeem_paused,
     })
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn migrate(_deps: DepsMut, _env: Env, _msg: MigrateMsg) -> StdResult<Response> {
-    Ok(Response::default())
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> Result<Response, ContractError> {
+    migrate_contract(deps, env, msg)
 }
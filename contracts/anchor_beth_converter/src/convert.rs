@@ -0,0 +1,576 @@
+use crate::backing::assert_source_backed;
+use crate::contract::load_source_token;
+use crate::error::ContractError;
+use crate::math::{
+    compute_fee, convert_to_anchor_decimals, convert_to_wormhole_decimals, min_amount_before_fee,
+    min_convertible_amount,
+};
+use crate::rate_limit::{
+    check_mint_capacity, check_redeem_capacity, consume_mint_capacity, consume_redeem_capacity,
+};
+use crate::state::{
+    read_config, read_native_source, read_pause_status, store_native_source, store_source_token,
+    SourceToken,
+};
+use crate::stats::{record_convert_to_anchor, record_convert_to_wormhole};
+
+use beth::converter::{Forward, SimulationResponse};
+use beth::deduct_tax;
+use cosmwasm_std::{
+    to_binary, Api, BankMsg, CanonicalAddr, Coin, CosmosMsg, Deps, DepsMut, Env, Response,
+    StdResult, Storage, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+/// A registered conversion source, either a cw20 token or a native denom
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Source {
+    Token(CanonicalAddr),
+    Native(String),
+}
+
+impl Source {
+    /// Resolve a source token address or native denom,
+    /// defaulting to the registered wormhole token
+    pub fn resolve(deps: Deps, source_token: Option<String>) -> Result<Source, ContractError> {
+        match source_token {
+            Some(denom) if read_native_source(deps.storage, &denom)?.is_some() => {
+                Ok(Source::Native(denom))
+            }
+            Some(source_token) => Ok(Source::Token(deps.api.addr_canonicalize(&source_token)?)),
+            None => read_config(deps.storage)?
+                .wormhole_token_address
+                .map(Source::Token)
+                .ok_or(ContractError::TokensNotRegistered {}),
+        }
+    }
+
+    pub fn load(&self, deps: Deps) -> Result<SourceToken, ContractError> {
+        match self {
+            Source::Token(token_address) => load_source_token(deps, token_address),
+            Source::Native(denom) => read_native_source(deps.storage, denom)?.ok_or_else(|| {
+                ContractError::SourceNotRegistered {
+                    token: denom.clone(),
+                }
+            }),
+        }
+    }
+
+    pub fn store(&self, storage: &mut dyn Storage, source: &SourceToken) -> StdResult<()> {
+        match self {
+            Source::Token(token_address) => store_source_token(storage, token_address, source),
+            Source::Native(denom) => store_native_source(storage, denom, source),
+        }
+    }
+
+    pub fn name(&self, api: &dyn Api) -> Result<String, ContractError> {
+        match self {
+            Source::Token(token_address) => Ok(api.addr_humanize(token_address)?.to_string()),
+            Source::Native(denom) => Ok(denom.clone()),
+        }
+    }
+
+    /// Amount received when paying out the source, native coins are sent net of tax
+    pub fn net_amount(&self, deps: Deps, amount: Uint128) -> Result<Uint128, ContractError> {
+        match self {
+            Source::Token(_) => Ok(amount),
+            Source::Native(denom) => Ok(deduct_tax(
+                deps,
+                Coin {
+                    denom: denom.clone(),
+                    amount,
+                },
+            )?
+            .amount),
+        }
+    }
+
+    /// Message delivering the source to the destination, native coins are sent net of tax
+    pub fn payout_msg(
+        &self,
+        deps: Deps,
+        destination: &Destination,
+        amount: Uint128,
+    ) -> Result<CosmosMsg, ContractError> {
+        match self {
+            Source::Token(token_address) => Ok(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: deps.api.addr_humanize(token_address)?.to_string(),
+                msg: to_binary(&match destination {
+                    Destination::Account(recipient) => Cw20ExecuteMsg::Transfer {
+                        recipient: recipient.clone(),
+                        amount,
+                    },
+                    Destination::Forward(forward) => Cw20ExecuteMsg::Send {
+                        contract: forward.contract.clone(),
+                        amount,
+                        msg: forward.msg.clone(),
+                    },
+                })?,
+                funds: vec![],
+            })),
+            Source::Native(denom) => {
+                let coin = deduct_tax(
+                    deps,
+                    Coin {
+                        denom: denom.clone(),
+                        amount,
+                    },
+                )?;
+                Ok(match destination {
+                    Destination::Account(recipient) => CosmosMsg::Bank(BankMsg::Send {
+                        to_address: recipient.clone(),
+                        amount: vec![coin],
+                    }),
+                    Destination::Forward(forward) => CosmosMsg::Wasm(WasmMsg::Execute {
+                        contract_addr: forward.contract.clone(),
+                        msg: forward.msg.clone(),
+                        funds: vec![coin],
+                    }),
+                })
+            }
+        }
+    }
+}
+
+/// Where the converted tokens are delivered
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Destination {
+    Account(String),
+    Forward(Forward),
+}
+
+impl Destination {
+    /// The sender receives the converted tokens unless a recipient or a forward is given
+    pub fn new(
+        api: &dyn Api,
+        sender: String,
+        recipient: Option<String>,
+        forward: Option<Forward>,
+    ) -> Result<Destination, ContractError> {
+        match (recipient, forward) {
+            (Some(_), Some(_)) => Err(ContractError::RecipientAndForward {}),
+            (Some(recipient), None) => Ok(Destination::Account(
+                api.addr_validate(&recipient)?.to_string(),
+            )),
+            (None, Some(forward)) => {
+                api.addr_validate(&forward.contract)?;
+                Ok(Destination::Forward(forward))
+            }
+            (None, None) => Ok(Destination::Account(sender)),
+        }
+    }
+
+    pub fn address(&self) -> &str {
+        match self {
+            Destination::Account(recipient) => recipient,
+            Destination::Forward(forward) => &forward.contract,
+        }
+    }
+}
+
+/// Amounts of a source token to anchor token conversion
+pub(crate) struct ConvertToAnchor {
+    pub anchor_token: CanonicalAddr,
+    pub fee_collector: Option<CanonicalAddr>,
+    pub source: SourceToken,
+    pub fee_amount: Uint128,
+    pub convert_amount: Uint128,
+    pub mint_amount: Uint128,
+    pub refund_amount: Uint128,
+    pub min_amount: Uint128,
+}
+
+/// Amounts of an anchor token to source token conversion
+pub(crate) struct ConvertToWormhole {
+    pub anchor_token: CanonicalAddr,
+    pub fee_collector: Option<CanonicalAddr>,
+    pub source: SourceToken,
+    pub fee_amount: Uint128,
+    pub return_amount: Uint128,
+    pub burn_amount: Uint128,
+    pub refund_amount: Uint128,
+    pub min_amount: Uint128,
+}
+
+/// Compute a conversion to anchor token without changing any state,
+/// so that simulations and executions produce the same numbers or the same error
+pub(crate) fn compute_convert_to_anchor(
+    deps: Deps,
+    env: &Env,
+    source_token: &Source,
+    amount: Uint128,
+) -> Result<ConvertToAnchor, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if read_pause_status(deps.storage)?.mint_paused {
+        return Err(ContractError::MintPaused {});
+    }
+
+    if config.anchor_token_address.is_none() || config.anchor_decimals.is_none() {
+        return Err(ContractError::TokensNotRegistered {});
+    }
+    let anchor_decimals = config
+        .anchor_decimals
+        .ok_or(ContractError::TokensNotRegistered {})?;
+
+    let source = source_token.load(deps)?;
+    if !source.enabled {
+        return Err(ContractError::SourceDisabled {
+            token: source_token.name(deps.api)?,
+        });
+    }
+
+    // the fee is charged in the deposited source token
+    let fee_amount = compute_fee(amount, config.to_anchor_fee_bps);
+    let convert_amount = amount.checked_sub(fee_amount)?;
+
+    // should convert to anchor decimals
+    let mint_amount = convert_to_anchor_decimals(convert_amount, anchor_decimals, source.decimals)?;
+    check_mint_capacity(deps.storage, &env.block, mint_amount)?;
+
+    // only the part of the amount that maps exactly onto whole anchor units is kept
+    // as backing, the remainder is refunded to the sender
+    let backing_amount =
+        convert_to_wormhole_decimals(mint_amount, anchor_decimals, source.decimals)?;
+    let refund_amount = convert_amount.checked_sub(backing_amount)?;
+
+    let min_amount = min_amount_before_fee(
+        min_convertible_amount(source.decimals, anchor_decimals),
+        config.to_anchor_fee_bps,
+    );
+
+    Ok(ConvertToAnchor {
+        anchor_token: config
+            .anchor_token_address
+            .ok_or(ContractError::TokensNotRegistered {})?,
+        fee_collector: config.fee_collector,
+        source,
+        fee_amount,
+        convert_amount: backing_amount,
+        mint_amount,
+        refund_amount,
+        min_amount,
+    })
+}
+
+/// Compute a conversion to a source token without changing any state,
+/// so that simulations and executions produce the same numbers or the same error
+pub(crate) fn compute_convert_to_wormhole(
+    deps: Deps,
+    env: &Env,
+    source_token: &Source,
+    amount: Uint128,
+) -> Result<ConvertToWormhole, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if read_pause_status(deps.storage)?.redeem_paused {
+        return Err(ContractError::RedeemPaused {});
+    }
+
+    if config.wormhole_token_address.is_none()
+        || config.anchor_token_address.is_none()
+        || config.anchor_decimals.is_none()
+    {
+        return Err(ContractError::TokensNotRegistered {});
+    }
+    let anchor_decimals = config
+        .anchor_decimals
+        .ok_or(ContractError::TokensNotRegistered {})?;
+
+    let source = source_token.load(deps)?;
+
+    // the fee is charged in anchor token before converting
+    let fee_amount = compute_fee(amount, config.to_wormhole_fee_bps);
+    let convert_amount = amount.checked_sub(fee_amount)?;
+
+    // should convert to wormhole decimals
+    let return_amount =
+        convert_to_wormhole_decimals(convert_amount, anchor_decimals, source.decimals)?;
+
+    // each source can only return what has been deposited through it
+    if return_amount > source.backing {
+        return Err(ContractError::InsufficientBacking {
+            backing: source.backing,
+        });
+    }
+
+    // only the part of the amount that maps exactly onto whole wormhole units is burned,
+    // the remainder is refunded to the sender
+    let burn_amount = convert_to_anchor_decimals(return_amount, anchor_decimals, source.decimals)?;
+    let refund_amount = convert_amount.checked_sub(burn_amount)?;
+    check_redeem_capacity(deps.storage, &env.block, burn_amount)?;
+    assert_source_backed(deps, env, source_token, &source)?;
+
+    let min_amount = min_amount_before_fee(
+        min_convertible_amount(anchor_decimals, source.decimals),
+        config.to_wormhole_fee_bps,
+    );
+
+    Ok(ConvertToWormhole {
+        anchor_token: config
+            .anchor_token_address
+            .ok_or(ContractError::TokensNotRegistered {})?,
+        fee_collector: config.fee_collector,
+        source,
+        fee_amount,
+        return_amount,
+        burn_amount,
+        refund_amount,
+        min_amount,
+    })
+}
+
+/// Source token being converted to anchor token
+pub(crate) enum SourceFunds {
+    /// sent to the converter, the part that cannot be converted is refunded to the sender
+    Received { sender: String },
+    /// held by the owner, who allowed the converter to transfer it
+    Allowance { owner: String },
+}
+
+/// Anchor token being converted to a source token
+pub(crate) enum AnchorFunds {
+    /// sent to the converter, the part that cannot be converted is refunded to the sender
+    Received { sender: String },
+    /// held by the owner, who allowed the converter to burn and transfer it
+    Allowance { owner: String },
+}
+
+pub(crate) fn execute_convert_to_anchor(
+    deps: DepsMut,
+    env: Env,
+    source_token: Source,
+    amount: Uint128,
+    funds: SourceFunds,
+    destination: Destination,
+) -> Result<Response, ContractError> {
+    let ConvertToAnchor {
+        anchor_token,
+        fee_collector,
+        mut source,
+        fee_amount,
+        convert_amount,
+        mint_amount,
+        refund_amount,
+        ..
+    } = compute_convert_to_anchor(deps.as_ref(), &env, &source_token, amount)?;
+
+    consume_mint_capacity(deps.storage, &env.block, mint_amount)?;
+
+    source.backing += convert_amount;
+    source_token.store(deps.storage, &source)?;
+    let deposit_amount = amount.checked_sub(refund_amount)?;
+    record_convert_to_anchor(deps.storage, &source_token, deposit_amount, mint_amount)?;
+
+    let anchor_token_addr = deps.api.addr_humanize(&anchor_token)?;
+
+    // source token drawn from an allowance is pulled before anything is minted or paid out,
+    // and never beyond what is converted or charged
+    let mut messages = vec![];
+    if let SourceFunds::Allowance { owner } = &funds {
+        let token_address = match &source_token {
+            Source::Token(token_address) => deps.api.addr_humanize(token_address)?,
+            Source::Native(denom) => {
+                return Err(ContractError::NativeAllowance {
+                    denom: denom.clone(),
+                })
+            }
+        };
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: token_address.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                owner: owner.clone(),
+                recipient: env.contract.address.to_string(),
+                amount: deposit_amount,
+            })?,
+            funds: vec![],
+        }));
+    }
+
+    // forwarded anchor token is minted to the converter and sent on in the same transaction
+    match &destination {
+        Destination::Account(recipient) => messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token_addr.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: recipient.clone(),
+                amount: mint_amount,
+            })?,
+            funds: vec![],
+        })),
+        Destination::Forward(forward) => messages.extend(vec![
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: anchor_token_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: env.contract.address.to_string(),
+                    amount: mint_amount,
+                })?,
+                funds: vec![],
+            }),
+            CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: anchor_token_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: forward.contract.clone(),
+                    amount: mint_amount,
+                    msg: forward.msg.clone(),
+                })?,
+                funds: vec![],
+            }),
+        ]),
+    };
+    if let SourceFunds::Received { sender } = &funds {
+        if !refund_amount.is_zero() {
+            messages.push(source_token.payout_msg(
+                deps.as_ref(),
+                &Destination::Account(sender.clone()),
+                refund_amount,
+            )?);
+        }
+    }
+    if !fee_amount.is_zero() {
+        let fee_collector = deps
+            .api
+            .addr_humanize(&fee_collector.ok_or(ContractError::FeeCollectorNotSet {})?)?;
+        messages.push(source_token.payout_msg(
+            deps.as_ref(),
+            &Destination::Account(fee_collector.to_string()),
+            fee_amount,
+        )?);
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "convert-to-anchor"),
+        ("recipient", destination.address()),
+        ("source_token", &source_token.name(deps.api)?),
+        ("minted_amount", &mint_amount.to_string()),
+        ("fee_amount", &fee_amount.to_string()),
+        ("refund_amount", &refund_amount.to_string()),
+    ]))
+}
+
+pub(crate) fn execute_convert_to_wormhole(
+    deps: DepsMut,
+    env: Env,
+    amount: Uint128,
+    funds: AnchorFunds,
+    source_token: Source,
+    destination: Destination,
+) -> Result<Response, ContractError> {
+    let ConvertToWormhole {
+        anchor_token,
+        fee_collector,
+        mut source,
+        fee_amount,
+        return_amount,
+        burn_amount,
+        refund_amount,
+        ..
+    } = compute_convert_to_wormhole(deps.as_ref(), &env, &source_token, amount)?;
+
+    consume_redeem_capacity(deps.storage, &env.block, burn_amount)?;
+
+    source.backing = source.backing.checked_sub(return_amount)?;
+    source_token.store(deps.storage, &source)?;
+    record_convert_to_wormhole(deps.storage, &source_token, return_amount, burn_amount)?;
+
+    let anchor_token_addr = deps.api.addr_humanize(&anchor_token)?;
+
+    let burn_msg = match &funds {
+        AnchorFunds::Received { .. } => Cw20ExecuteMsg::Burn {
+            amount: burn_amount,
+        },
+        AnchorFunds::Allowance { owner } => Cw20ExecuteMsg::BurnFrom {
+            owner: owner.clone(),
+            amount: burn_amount,
+        },
+    };
+    let mut messages = vec![
+        source_token.payout_msg(deps.as_ref(), &destination, return_amount)?,
+        CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token_addr.to_string(),
+            msg: to_binary(&burn_msg)?,
+            funds: vec![],
+        }),
+    ];
+    // anchor token drawn from an allowance is never pulled beyond what is burned or charged
+    if let AnchorFunds::Received { sender } = &funds {
+        if !refund_amount.is_zero() {
+            messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: anchor_token_addr.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: sender.clone(),
+                    amount: refund_amount,
+                })?,
+                funds: vec![],
+            }));
+        }
+    }
+    if !fee_amount.is_zero() {
+        let fee_collector = deps
+            .api
+            .addr_humanize(&fee_collector.ok_or(ContractError::FeeCollectorNotSet {})?)?
+            .to_string();
+        let fee_msg = match &funds {
+            AnchorFunds::Received { .. } => Cw20ExecuteMsg::Transfer {
+                recipient: fee_collector,
+                amount: fee_amount,
+            },
+            AnchorFunds::Allowance { owner } => Cw20ExecuteMsg::TransferFrom {
+                owner: owner.clone(),
+                recipient: fee_collector,
+                amount: fee_amount,
+            },
+        };
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: anchor_token_addr.to_string(),
+            msg: to_binary(&fee_msg)?,
+            funds: vec![],
+        }));
+    }
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "convert-to-wormhole"),
+        ("recipient", destination.address()),
+        ("source_token", &source_token.name(deps.api)?),
+        ("return_amount", &return_amount.to_string()),
+        ("burn_amount", &burn_amount.to_string()),
+        ("refund_amount", &refund_amount.to_string()),
+        ("fee_amount", &fee_amount.to_string()),
+    ]))
+}
+
+pub fn query_simulate_convert_to_anchor(
+    deps: Deps,
+    env: Env,
+    source_token: Option<String>,
+    amount: Uint128,
+) -> Result<SimulationResponse, ContractError> {
+    let source_token = Source::resolve(deps, source_token)?;
+    let convert = compute_convert_to_anchor(deps, &env, &source_token, amount)?;
+
+    Ok(SimulationResponse {
+        return_amount: convert.mint_amount,
+        // the source refunded is paid out like any other, native coins net of tax
+        refund_amount: source_token.net_amount(deps, convert.refund_amount)?,
+        fee_amount: convert.fee_amount,
+        min_amount: convert.min_amount,
+    })
+}
+
+pub fn query_simulate_convert_to_wormhole(
+    deps: Deps,
+    env: Env,
+    source_token: Option<String>,
+    amount: Uint128,
+) -> Result<SimulationResponse, ContractError> {
+    let source_token = Source::resolve(deps, source_token)?;
+    let convert = compute_convert_to_wormhole(deps, &env, &source_token, amount)?;
+
+    Ok(SimulationResponse {
+        // native coins are received net of tax
+        return_amount: source_token.net_amount(deps, convert.return_amount)?,
+        // the refund is anchor token, which is never taxed
+        refund_amount: convert.refund_amount,
+        fee_amount: convert.fee_amount,
+        min_amount: convert.min_amount,
+    })
+}
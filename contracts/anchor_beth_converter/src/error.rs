@@ -0,0 +1,150 @@
+use cosmwasm_std::{OverflowError, StdError, Uint128};
+use thiserror::Error;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ContractError {
+    #[error("{0}")]
+    Std(#[from] StdError),
+
+    #[error("{0}")]
+    Overflow(#[from] OverflowError),
+
+    #[error("unauthorized")]
+    Unauthorized {},
+
+    #[error("wormhole or anchor token must be registered first")]
+    TokensNotRegistered {},
+
+    #[error("cannot convert; conversion is only possible for amounts of at least {min}")]
+    AmountTooSmall { min: Uint128 },
+
+    #[error("cannot convert a zero amount")]
+    ZeroAmount {},
+
+    #[error("unknown cw20 hook message")]
+    UnknownHook {},
+
+    #[error("minting is paused")]
+    MintPaused {},
+
+    #[error("redemption is paused")]
+    RedeemPaused {},
+
+    #[error("source token {token} is not registered")]
+    SourceNotRegistered { token: String },
+
+    #[error("source token {token} is already registered")]
+    SourceAlreadyRegistered { token: String },
+
+    #[error("source token {token} is disabled")]
+    SourceDisabled { token: String },
+
+    #[error("the registered wormhole token cannot be removed")]
+    WormholeTokenRemoval {},
+
+    #[error("source token still backs {backing} deposited tokens")]
+    SourceStillBacking { backing: Uint128 },
+
+    #[error("insufficient backing; cannot return more than {backing} source token")]
+    InsufficientBacking { backing: Uint128 },
+
+    #[error("redemption would leave anchor token undercollateralized")]
+    Undercollateralized {},
+
+    #[error("no wormhole token surplus to sweep")]
+    NoSurplus {},
+
+    #[error("cannot recover source token {token}")]
+    CannotRecoverSource { token: String },
+
+    #[error("no token balance to recover")]
+    NothingToRecover {},
+
+    #[error("conversion fee cannot exceed {max_bps} bps")]
+    FeeTooHigh { max_bps: u64 },
+
+    #[error("fee collector must be set to charge a conversion fee")]
+    FeeCollectorNotSet {},
+
+    #[error("rate limit window cannot be empty")]
+    EmptyRateLimitWindow {},
+
+    #[error(
+        "mint rate limit exceeded; {remaining} anchor token can be minted in the current window"
+    )]
+    MintRateLimitExceeded { remaining: Uint128 },
+
+    #[error(
+        "redeem rate limit exceeded; {remaining} anchor token can be redeemed in the current window"
+    )]
+    RedeemRateLimitExceeded { remaining: Uint128 },
+
+    #[error("exactly one native coin must be sent")]
+    InvalidNativeFunds {},
+
+    #[error("native source {denom} cannot be converted from an allowance")]
+    NativeAllowance { denom: String },
+
+    #[error("sender {sender} is denylisted")]
+    SenderDenied { sender: String },
+
+    #[error("sender {sender} is not allowlisted")]
+    SenderNotAllowed { sender: String },
+
+    #[error("cannot set both a recipient and a forward")]
+    RecipientAndForward {},
+
+    #[error("no ownership transfer is pending")]
+    NoPendingOwner {},
+
+    #[error("ownership proposal has expired")]
+    OwnershipProposalExpired {},
+
+    #[error("ownership proposal expiry is out of range")]
+    InvalidOwnershipExpiry {},
+
+    #[error("a token migration is already pending")]
+    TokenMigrationAlreadyPending {},
+
+    #[error("no token migration is pending")]
+    NoPendingTokenMigration {},
+
+    #[error("no token migration has been proposed")]
+    NoTokenMigration {},
+
+    #[error("token migration is timelocked until {eta}")]
+    TokenMigrationTimelocked { eta: u64 },
+
+    #[error("migration returned {balance} new token, expected {expected}")]
+    TokenMigrationShortfall { balance: Uint128, expected: Uint128 },
+
+    #[error("cannot migrate from contract {found}, expected {expected}")]
+    InvalidContractName { expected: String, found: String },
+
+    #[error("cannot downgrade from version {from} to {to}")]
+    CannotDowngrade { from: String, to: String },
+
+    #[error("invalid contract version {version}")]
+    InvalidVersion { version: String },
+
+    #[error("owner operations must be queued through the timelock")]
+    MustBeQueued {},
+
+    #[error("operation cannot be queued")]
+    NotTimelocked {},
+
+    #[error("no queued action with id {id}")]
+    QueuedActionNotFound { id: u64 },
+
+    #[error("queued action is timelocked until {eta}")]
+    ActionTimelocked { eta: u64 },
+
+    #[error("queued action eta is out of range")]
+    InvalidActionEta {},
+
+    #[error("queued action was proposed by a previous owner")]
+    StaleQueuedAction {},
+
+    #[error("timelock delay cannot exceed {max} seconds")]
+    TimelockDelayTooLong { max: u64 },
+}
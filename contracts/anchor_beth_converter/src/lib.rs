@@ -1,7 +1,18 @@
+pub mod access;
+pub mod backing;
 pub mod contract;
+pub mod convert;
+pub mod error;
 pub mod math;
+pub mod migration;
+pub mod ownership;
 pub mod querier;
+pub mod rate_limit;
+pub mod recovery;
 pub mod state;
+pub mod stats;
+pub mod timelock;
+pub mod token_migration;
 
 #[cfg(test)]
 mod testing;
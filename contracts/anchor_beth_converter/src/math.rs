@@ -1,25 +1,28 @@
-use cosmwasm_std::{StdError, StdResult, Uint128};
+use crate::error::ContractError;
+
+use cosmwasm_std::{StdError, Uint128};
 
 pub(crate) fn convert_to_wormhole_decimals(
     amount: Uint128,
     anchor_decimals: u8,
     wormhole_decimals: u8,
-) -> StdResult<Uint128> {
+) -> Result<Uint128, ContractError> {
     if anchor_decimals > wormhole_decimals {
         let decimal_fraction =
             Uint128::new(10u128).saturating_pow((anchor_decimals - wormhole_decimals) as u32);
-        let result = amount.checked_div(decimal_fraction);
-        if result.as_ref().unwrap().is_zero() {
-            return Err(StdError::generic_err(format!(
-                "cannot convert; conversion is only possible for amounts greater than {} anchor token",
-                decimal_fraction
-            )));
+        let result = amount
+            .checked_div(decimal_fraction)
+            .map_err(StdError::from)?;
+        if result.is_zero() {
+            return Err(ContractError::AmountTooSmall {
+                min: decimal_fraction,
+            });
         }
-        Ok(result.unwrap())
+        Ok(result)
     } else {
         let decimal_fraction =
             Uint128::new(10u128).saturating_pow((wormhole_decimals - anchor_decimals) as u32);
-        Ok(amount.checked_mul(decimal_fraction).unwrap())
+        Ok(amount.checked_mul(decimal_fraction)?)
     }
 }
 
@@ -27,26 +30,66 @@ pub(crate) fn convert_to_anchor_decimals(
     amount: Uint128,
     anchor_decimals: u8,
     wormhole_decimals: u8,
-) -> StdResult<Uint128> {
+) -> Result<Uint128, ContractError> {
     if anchor_decimals > wormhole_decimals {
         let decimal_fraction =
             Uint128::new(10u128).saturating_pow((anchor_decimals - wormhole_decimals) as u32);
 
-        Ok(amount.checked_mul(decimal_fraction).unwrap())
+        Ok(amount.checked_mul(decimal_fraction)?)
     } else {
         let decimal_fraction =
             Uint128::new(10u128).saturating_pow((wormhole_decimals - anchor_decimals) as u32);
-        let result = amount.checked_div(decimal_fraction);
-        if result.as_ref().unwrap().is_zero() {
-            return Err(StdError::generic_err(format!(
-                "cannot convert; conversion is only possible for amounts greater than {} wormhole token",
-                decimal_fraction
-            )));
+        let result = amount
+            .checked_div(decimal_fraction)
+            .map_err(StdError::from)?;
+        if result.is_zero() {
+            return Err(ContractError::AmountTooSmall {
+                min: decimal_fraction,
+            });
         }
-        Ok(result.unwrap())
+        Ok(result)
+    }
+}
+
+/// return the amount expressed with other decimals, rounded down
+pub(crate) fn convert_decimals(amount: Uint128, decimals: u8, to_decimals: u8) -> Uint128 {
+    if decimals > to_decimals {
+        let decimal_fraction = Uint128::new(10u128).saturating_pow((decimals - to_decimals) as u32);
+        amount.multiply_ratio(1u128, decimal_fraction)
+    } else {
+        normalize_decimals(amount, decimals, to_decimals)
+    }
+}
+
+/// return the amount expressed with more decimals
+pub(crate) fn normalize_decimals(amount: Uint128, decimals: u8, to_decimals: u8) -> Uint128 {
+    let decimal_fraction =
+        Uint128::new(10u128).saturating_pow(to_decimals.saturating_sub(decimals) as u32);
+    amount.saturating_mul(decimal_fraction)
+}
+
+/// return the smallest amount which converts to at least one unit of the other token
+pub(crate) fn min_convertible_amount(from_decimals: u8, to_decimals: u8) -> Uint128 {
+    if from_decimals > to_decimals {
+        Uint128::new(10u128).saturating_pow((from_decimals - to_decimals) as u32)
+    } else {
+        Uint128::new(1u128)
     }
 }
 
+/// return the smallest amount which is still at least min_amount once the fee is deducted
+pub(crate) fn min_amount_before_fee(min_amount: Uint128, fee_bps: u64) -> Uint128 {
+    // amount - floor(amount * fee_bps / 10000) >= min_amount
+    // <=> amount > (min_amount - 1) * 10000 / (10000 - fee_bps)
+    (min_amount - Uint128::new(1u128)).multiply_ratio(10000u64, 10000u64 - fee_bps)
+        + Uint128::new(1u128)
+}
+
+/// return the fee for the given basis points, rounded down
+pub(crate) fn compute_fee(amount: Uint128, fee_bps: u64) -> Uint128 {
+    amount.multiply_ratio(fee_bps, 10000u64)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -68,4 +111,30 @@ mod tests {
         let d = convert_to_anchor_decimals(a, b, c).unwrap();
         assert_eq!(d, Uint128::new(1000000));
     }
+
+    #[test]
+    fn test_compute_fee() {
+        assert_eq!(
+            compute_fee(Uint128::new(100000000), 30),
+            Uint128::new(300000)
+        );
+        assert_eq!(compute_fee(Uint128::new(333), 30), Uint128::zero());
+        assert_eq!(compute_fee(Uint128::new(100000000), 0), Uint128::zero());
+    }
+
+    #[test]
+    fn test_min_amount_before_fee() {
+        let min_amount = min_convertible_amount(8, 6);
+        assert_eq!(min_amount, Uint128::new(100));
+        assert_eq!(min_convertible_amount(6, 8), Uint128::new(1));
+
+        assert_eq!(min_amount_before_fee(min_amount, 0), Uint128::new(100));
+        let amount = min_amount_before_fee(min_amount, 30);
+        assert_eq!(amount, Uint128::new(100));
+        let amount = min_amount_before_fee(Uint128::new(1000), 30);
+        assert_eq!(amount, Uint128::new(1003));
+        assert_eq!(amount - compute_fee(amount, 30), Uint128::new(1000));
+        let amount = amount - Uint128::new(1);
+        assert!(amount - compute_fee(amount, 30) < Uint128::new(1000));
+    }
 }
@@ -0,0 +1,123 @@
+use crate::error::ContractError;
+use crate::querier::{query_decimals, query_token_balance};
+use crate::state::{read_config, read_source_token, store_config, store_source_token, SourceToken};
+
+use beth::converter::{MigrateMsg, MigrationParams};
+use cosmwasm_std::{DepsMut, Env, Response, StdError, Storage};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+
+pub const CONTRACT_NAME: &str = "crates.io:anchor_beth_converter";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// instances deployed before the version was stored are treated as this version
+const LEGACY_VERSION: &str = "0.0.0";
+
+type Migration = fn(DepsMut, &Env, &MigrateMsg) -> Result<(), ContractError>;
+
+/// state migrations in ascending order, each tagged with the version introducing it
+const MIGRATIONS: &[(&str, Migration)] = &[("0.1.0", migrate_v010)];
+
+pub fn migrate_contract(
+    mut deps: DepsMut,
+    env: Env,
+    msg: MigrateMsg,
+) -> Result<Response, ContractError> {
+    let stored = read_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_string(),
+            found: stored.contract,
+        });
+    }
+
+    let from = parse_version(&stored.version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(ContractError::CannotDowngrade {
+            from: stored.version,
+            to: CONTRACT_VERSION.to_string(),
+        });
+    }
+
+    // run every migration between the stored version and this one
+    for (version, migration) in MIGRATIONS {
+        let version = parse_version(version)?;
+        if from < version && version <= to {
+            migration(deps.branch(), &env, &msg)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "migrate"),
+        ("from_version", &stored.version),
+        ("to_version", CONTRACT_VERSION),
+    ]))
+}
+
+fn read_contract_version(storage: &dyn Storage) -> Result<ContractVersion, ContractError> {
+    match get_contract_version(storage) {
+        Ok(version) => Ok(version),
+        Err(StdError::NotFound { .. }) => Ok(ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: LEGACY_VERSION.to_string(),
+        }),
+        Err(err) => Err(err.into()),
+    }
+}
+
+/// parse a `major.minor.patch` version so it can be ordered
+fn parse_version(version: &str) -> Result<(u64, u64, u64), ContractError> {
+    let mut parts = version.split('.').map(|part| part.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(ContractError::InvalidVersion {
+            version: version.to_string(),
+        }),
+    }
+}
+
+/// backfill the state of instances deployed before the anchor decimals
+/// and the source token registry were stored
+fn migrate_v010(deps: DepsMut, env: &Env, msg: &MigrateMsg) -> Result<(), ContractError> {
+    let mut config = read_config(deps.storage)?;
+
+    if let Some(anchor_token_raw) = &config.anchor_token_address {
+        if config.anchor_decimals.is_none() {
+            let anchor_token = deps.api.addr_humanize(anchor_token_raw)?;
+            config.anchor_decimals = Some(query_decimals(deps.as_ref(), anchor_token)?);
+            store_config(deps.storage).save(&config)?;
+        }
+    }
+
+    // these instances only know the wormhole token; unless told otherwise,
+    // everything the converter holds of it backs the anchor tokens minted so far
+    if let Some(wormhole_token_raw) = config.wormhole_token_address {
+        if read_source_token(deps.storage, &wormhole_token_raw)?.is_none() {
+            let wormhole_token = deps.api.addr_humanize(&wormhole_token_raw)?;
+            let decimals = query_decimals(deps.as_ref(), wormhole_token.clone())?;
+            let backing = match msg.params.iter().find_map(|params| match params {
+                MigrationParams::V010 { wormhole_backing } => *wormhole_backing,
+            }) {
+                Some(backing) => backing,
+                None => query_token_balance(
+                    deps.as_ref(),
+                    wormhole_token,
+                    env.contract.address.clone(),
+                )?,
+            };
+            store_source_token(
+                deps.storage,
+                &wormhole_token_raw,
+                &SourceToken {
+                    decimals,
+                    enabled: true,
+                    backing,
+                },
+            )?;
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,105 @@
+use crate::error::ContractError;
+use crate::state::{
+    read_config, read_pending_owner, remove_pending_owner, store_config, store_pending_owner,
+    PendingOwner,
+};
+
+use beth::converter::PendingOwnerResponse;
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+pub fn propose_new_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: String,
+    expires_in: Option<u64>,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    // a new proposal replaces the pending one
+    let expires_at = expires_in
+        .map(|expires_in| {
+            env.block
+                .time
+                .seconds()
+                .checked_add(expires_in)
+                .ok_or(ContractError::InvalidOwnershipExpiry {})
+        })
+        .transpose()?;
+    store_pending_owner(
+        deps.storage,
+        &PendingOwner {
+            owner: deps.api.addr_canonicalize(&new_owner)?,
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose_new_owner"),
+        ("new_owner", &new_owner),
+    ]))
+}
+
+pub fn cancel_ownership_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if read_pending_owner(deps.storage)?.is_none() {
+        return Err(ContractError::NoPendingOwner {});
+    }
+    remove_pending_owner(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![("action", "cancel_ownership_transfer")]))
+}
+
+pub fn accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let pending_owner =
+        read_pending_owner(deps.storage)?.ok_or(ContractError::NoPendingOwner {})?;
+
+    if pending_owner.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if let Some(expires_at) = pending_owner.expires_at {
+        if env.block.time.seconds() >= expires_at {
+            return Err(ContractError::OwnershipProposalExpired {});
+        }
+    }
+
+    let mut config = read_config(deps.storage)?;
+    config.owner = pending_owner.owner;
+    store_config(deps.storage).save(&config)?;
+    remove_pending_owner(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "accept_ownership"),
+        ("owner", info.sender.as_str()),
+    ]))
+}
+
+pub fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    Ok(match read_pending_owner(deps.storage)? {
+        Some(pending_owner) => PendingOwnerResponse {
+            pending_owner: Some(deps.api.addr_humanize(&pending_owner.owner)?.to_string()),
+            expires_at: pending_owner.expires_at,
+        },
+        None => PendingOwnerResponse {
+            pending_owner: None,
+            expires_at: None,
+        },
+    })
+}
@@ -1,5 +1,5 @@
-use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, WasmQuery};
-use cw20::{Cw20QueryMsg, TokenInfoResponse};
+use cosmwasm_std::{to_binary, Addr, Deps, QueryRequest, StdResult, Uint128, WasmQuery};
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
 
 pub fn query_decimals(deps: Deps, contract_addr: Addr) -> StdResult<u8> {
     // load price form the oracle
@@ -11,3 +11,28 @@ pub fn query_decimals(deps: Deps, contract_addr: Addr) -> StdResult<u8> {
 
     Ok(token_info.decimals)
 }
+
+pub fn query_total_supply(deps: Deps, contract_addr: Addr) -> StdResult<Uint128> {
+    let token_info: TokenInfoResponse =
+        deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+            contract_addr: contract_addr.to_string(),
+            msg: to_binary(&Cw20QueryMsg::TokenInfo {})?,
+        }))?;
+
+    Ok(token_info.total_supply)
+}
+
+pub fn query_token_balance(
+    deps: Deps,
+    contract_addr: Addr,
+    account_addr: Addr,
+) -> StdResult<Uint128> {
+    let balance: BalanceResponse = deps.querier.query(&QueryRequest::Wasm(WasmQuery::Smart {
+        contract_addr: contract_addr.to_string(),
+        msg: to_binary(&Cw20QueryMsg::Balance {
+            address: account_addr.to_string(),
+        })?,
+    }))?;
+
+    Ok(balance.balance)
+}
@@ -0,0 +1,250 @@
+use crate::error::ContractError;
+use crate::state::{read_config, read_rate_limit, store_rate_limit, RateLimit, RateLimitBucket};
+
+use beth::converter::RateLimitResponse;
+use cosmwasm_std::{BlockInfo, DepsMut, Env, MessageInfo, Response, Storage, Uint128};
+use cw0::Duration;
+
+// usage is counted in buckets of a tenth of the window,
+// so the window slides by steps of at most a tenth of its length
+const WINDOW_BUCKETS: u64 = 10;
+
+pub fn update_rate_limit(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    window: Duration,
+    mint_limit: Option<Uint128>,
+    redeem_limit: Option<Uint128>,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if window_length(&window) == 0 {
+        return Err(ContractError::EmptyRateLimitWindow {});
+    }
+
+    // keep the usage of the last window unless the window unit changes
+    let buckets = match read_rate_limit(deps.storage)? {
+        Some(rate_limit) if same_unit(&rate_limit.window, &window) => rate_limit.buckets,
+        _ => vec![],
+    };
+    let rate_limit = current_window(
+        RateLimit {
+            window,
+            mint_limit,
+            redeem_limit,
+            buckets,
+        },
+        &env.block,
+    );
+    store_rate_limit(deps.storage, &rate_limit)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "update_rate_limit"),
+        ("window", &window.to_string()),
+        (
+            "mint_limit",
+            &mint_limit
+                .map(|limit| limit.to_string())
+                .unwrap_or_default(),
+        ),
+        (
+            "redeem_limit",
+            &redeem_limit
+                .map(|limit| limit.to_string())
+                .unwrap_or_default(),
+        ),
+    ]))
+}
+
+/// Count the minted anchor amount against the current window
+pub(crate) fn consume_mint_capacity(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    match mint_capacity_after(storage, block, amount)? {
+        Some(rate_limit) => Ok(store_rate_limit(storage, &rate_limit)?),
+        None => Ok(()),
+    }
+}
+
+/// Check the minted anchor amount fits in the current window without counting it
+pub(crate) fn check_mint_capacity(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    mint_capacity_after(storage, block, amount).map(|_| ())
+}
+
+/// Count the burned anchor amount against the current window
+pub(crate) fn consume_redeem_capacity(
+    storage: &mut dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    match redeem_capacity_after(storage, block, amount)? {
+        Some(rate_limit) => Ok(store_rate_limit(storage, &rate_limit)?),
+        None => Ok(()),
+    }
+}
+
+/// Check the burned anchor amount fits in the current window without counting it
+pub(crate) fn check_redeem_capacity(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<(), ContractError> {
+    redeem_capacity_after(storage, block, amount).map(|_| ())
+}
+
+fn mint_capacity_after(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<Option<RateLimit>, ContractError> {
+    let mut rate_limit = match read_rate_limit(storage)? {
+        Some(rate_limit) => current_window(rate_limit, block),
+        None => return Ok(None),
+    };
+
+    let (minted, _) = usage(&rate_limit);
+    if let Some(mint_limit) = rate_limit.mint_limit {
+        if minted + amount > mint_limit {
+            return Err(ContractError::MintRateLimitExceeded {
+                remaining: mint_limit.saturating_sub(minted),
+            });
+        }
+    }
+    current_bucket(&mut rate_limit, block).minted += amount;
+
+    Ok(Some(rate_limit))
+}
+
+fn redeem_capacity_after(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+    amount: Uint128,
+) -> Result<Option<RateLimit>, ContractError> {
+    let mut rate_limit = match read_rate_limit(storage)? {
+        Some(rate_limit) => current_window(rate_limit, block),
+        None => return Ok(None),
+    };
+
+    let (_, redeemed) = usage(&rate_limit);
+    if let Some(redeem_limit) = rate_limit.redeem_limit {
+        if redeemed + amount > redeem_limit {
+            return Err(ContractError::RedeemRateLimitExceeded {
+                remaining: redeem_limit.saturating_sub(redeemed),
+            });
+        }
+    }
+    current_bucket(&mut rate_limit, block).redeemed += amount;
+
+    Ok(Some(rate_limit))
+}
+
+pub fn query_rate_limit(
+    storage: &dyn Storage,
+    block: &BlockInfo,
+) -> Result<RateLimitResponse, ContractError> {
+    let rate_limit = match read_rate_limit(storage)? {
+        Some(rate_limit) => current_window(rate_limit, block),
+        None => {
+            return Ok(RateLimitResponse {
+                window: None,
+                window_start: None,
+                mint_limit: None,
+                redeem_limit: None,
+                minted: Uint128::zero(),
+                redeemed: Uint128::zero(),
+                remaining_mint: None,
+                remaining_redeem: None,
+            })
+        }
+    };
+
+    let (minted, redeemed) = usage(&rate_limit);
+    Ok(RateLimitResponse {
+        window: Some(rate_limit.window),
+        window_start: Some(
+            rate_limit
+                .buckets
+                .first()
+                .map(|bucket| bucket.start)
+                .unwrap_or_else(|| window_position(&rate_limit.window, block)),
+        ),
+        mint_limit: rate_limit.mint_limit,
+        redeem_limit: rate_limit.redeem_limit,
+        minted,
+        redeemed,
+        remaining_mint: rate_limit
+            .mint_limit
+            .map(|limit| limit.saturating_sub(minted)),
+        remaining_redeem: rate_limit
+            .redeem_limit
+            .map(|limit| limit.saturating_sub(redeemed)),
+    })
+}
+
+// drop the usage which has slid out of the window
+fn current_window(mut rate_limit: RateLimit, block: &BlockInfo) -> RateLimit {
+    let position = window_position(&rate_limit.window, block);
+    let length = window_length(&rate_limit.window);
+    rate_limit
+        .buckets
+        .retain(|bucket| position.saturating_sub(bucket.start) < length);
+    rate_limit
+}
+
+/// Anchor token minted and redeemed within the window
+fn usage(rate_limit: &RateLimit) -> (Uint128, Uint128) {
+    rate_limit.buckets.iter().fold(
+        (Uint128::zero(), Uint128::zero()),
+        |(minted, redeemed), bucket| (minted + bucket.minted, redeemed + bucket.redeemed),
+    )
+}
+
+// count the conversion in the latest bucket unless it spans a tenth of the window already
+fn current_bucket<'a>(rate_limit: &'a mut RateLimit, block: &BlockInfo) -> &'a mut RateLimitBucket {
+    let position = window_position(&rate_limit.window, block);
+    let bucket_length = (window_length(&rate_limit.window) / WINDOW_BUCKETS).max(1);
+    let is_current = matches!(
+        rate_limit.buckets.last(),
+        Some(bucket) if position.saturating_sub(bucket.start) < bucket_length
+    );
+    if !is_current {
+        rate_limit.buckets.push(RateLimitBucket {
+            start: position,
+            minted: Uint128::zero(),
+            redeemed: Uint128::zero(),
+        });
+    }
+    rate_limit.buckets.last_mut().unwrap()
+}
+
+fn window_position(window: &Duration, block: &BlockInfo) -> u64 {
+    match window {
+        Duration::Height(_) => block.height,
+        Duration::Time(_) => block.time.seconds(),
+    }
+}
+
+fn window_length(window: &Duration) -> u64 {
+    match window {
+        Duration::Height(height) => *height,
+        Duration::Time(time) => *time,
+    }
+}
+
+fn same_unit(a: &Duration, b: &Duration) -> bool {
+    matches!(
+        (a, b),
+        (Duration::Height(_), Duration::Height(_)) | (Duration::Time(_), Duration::Time(_))
+    )
+}
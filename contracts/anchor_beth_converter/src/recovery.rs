@@ -0,0 +1,109 @@
+use crate::backing::compute_backing;
+use crate::contract::load_source_token;
+use crate::error::ContractError;
+use crate::math::convert_decimals;
+use crate::querier::query_token_balance;
+use crate::state::{read_config, read_source_token};
+
+use cosmwasm_std::{to_binary, DepsMut, Env, MessageInfo, Response, WasmMsg};
+use cw20::Cw20ExecuteMsg;
+
+/// Send the wormhole token held above what backs the anchor token supply
+pub fn sweep_surplus(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let wormhole_token_raw = config
+        .wormhole_token_address
+        .ok_or(ContractError::TokensNotRegistered {})?;
+    let wormhole_token = deps.api.addr_humanize(&wormhole_token_raw)?;
+    let source = load_source_token(deps.as_ref(), &wormhole_token_raw)?;
+
+    // the surplus over the anchor token supply, expressed in wormhole token units
+    let backing = compute_backing(deps.as_ref(), &env)?;
+    let surplus = convert_decimals(
+        backing
+            .wormhole_balance
+            .saturating_sub(backing.anchor_supply),
+        backing.decimals,
+        source.decimals,
+    );
+
+    // never take the wormhole token deposited for the anchor token still outstanding
+    let balance = query_token_balance(deps.as_ref(), wormhole_token.clone(), env.contract.address)?;
+    let amount = surplus.min(balance.saturating_sub(source.backing));
+    if amount.is_zero() {
+        return Err(ContractError::NoSurplus {});
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: wormhole_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "sweep_surplus"),
+            ("recipient", recipient.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
+
+/// Send the whole balance of a cw20 token which is not a conversion source
+pub fn recover_token(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    token_address: String,
+    recipient: String,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let token = deps.api.addr_validate(&token_address)?;
+    let recipient = deps.api.addr_validate(&recipient)?;
+    let token_raw = deps.api.addr_canonicalize(token.as_str())?;
+    if read_source_token(deps.storage, &token_raw)?.is_some()
+        || config.wormhole_token_address == Some(token_raw)
+    {
+        return Err(ContractError::CannotRecoverSource {
+            token: token.to_string(),
+        });
+    }
+
+    let amount = query_token_balance(deps.as_ref(), token.clone(), env.contract.address)?;
+    if amount.is_zero() {
+        return Err(ContractError::NothingToRecover {});
+    }
+
+    Ok(Response::new()
+        .add_message(WasmMsg::Execute {
+            contract_addr: token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: recipient.to_string(),
+                amount,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            ("action", "recover_token"),
+            ("token", token.as_str()),
+            ("recipient", recipient.as_str()),
+            ("amount", &amount.to_string()),
+        ]))
+}
@@ -1,21 +1,485 @@
-use cosmwasm_std::{CanonicalAddr, StdResult, Storage};
-use cosmwasm_storage::{singleton, singleton_read, Singleton};
+use cosmwasm_std::{Binary, CanonicalAddr, Deps, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{bucket, bucket_read, singleton, singleton_read, ReadonlyBucket, Singleton};
+use cw0::Duration;
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use beth::converter::{
+    ExecuteMsg, NativeSourceResponse, QueuedActionResponse, SourceTokenResponse,
+};
+
 pub static KEY_CONFIG: &[u8] = b"config";
+pub static KEY_PENDING_OWNER: &[u8] = b"pending_owner";
+pub static KEY_PAUSE_STATUS: &[u8] = b"pause_status";
+pub static KEY_RATE_LIMIT: &[u8] = b"rate_limit";
+pub static KEY_TOKEN_MIGRATION: &[u8] = b"token_migration";
+pub static KEY_STATS: &[u8] = b"stats";
+pub static KEY_ALLOWLIST_ONLY: &[u8] = b"allowlist_only";
+pub static KEY_TIMELOCK: &[u8] = b"timelock";
+
+pub static PREFIX_SOURCE_TOKENS: &[u8] = b"source_tokens";
+pub static PREFIX_NATIVE_SOURCES: &[u8] = b"native_sources";
+pub static PREFIX_SOURCE_STATS: &[u8] = b"source_stats";
+pub static PREFIX_NATIVE_STATS: &[u8] = b"native_stats";
+pub static PREFIX_ALLOWLIST: &[u8] = b"allowlist";
+pub static PREFIX_DENYLIST: &[u8] = b"denylist";
+pub static PREFIX_QUEUED_ACTIONS: &[u8] = b"queued_actions";
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Config {
     pub owner: CanonicalAddr,
     pub anchor_token_address: Option<CanonicalAddr>,
+    /// default source token, used for redemptions that do not name a source
     pub wormhole_token_address: Option<CanonicalAddr>,
+    pub anchor_decimals: Option<u8>,
+    pub fee_collector: Option<CanonicalAddr>,
+    /// fee charged in the source token, in basis points
+    #[serde(default)]
+    pub to_anchor_fee_bps: u64,
+    /// fee charged in the anchor token, in basis points
+    #[serde(default)]
+    pub to_wormhole_fee_bps: u64,
+    /// can pause conversions, but only the owner can unpause them
+    pub guardian: Option<CanonicalAddr>,
 }
 
-pub fn store_config(storage: &mut dyn Storage) -> Singleton<Config> {
+pub fn store_config(storage: &mut dyn Storage) -> Singleton<'_, Config> {
     singleton(storage, KEY_CONFIG)
 }
 
 pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
     singleton_read(storage, KEY_CONFIG).load()
 }
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    pub owner: CanonicalAddr,
+    /// time in seconds after which the proposal can no longer be accepted
+    pub expires_at: Option<u64>,
+}
+
+pub fn store_pending_owner(
+    storage: &mut dyn Storage,
+    pending_owner: &PendingOwner,
+) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_OWNER).save(pending_owner)
+}
+
+pub fn read_pending_owner(storage: &dyn Storage) -> StdResult<Option<PendingOwner>> {
+    singleton_read(storage, KEY_PENDING_OWNER).may_load()
+}
+
+pub fn remove_pending_owner(storage: &mut dyn Storage) {
+    singleton::<PendingOwner>(storage, KEY_PENDING_OWNER).remove()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct PauseStatus {
+    pub mint_paused: bool,
+    pub redeem_paused: bool,
+}
+
+pub fn store_pause_status(storage: &mut dyn Storage, pause_status: &PauseStatus) -> StdResult<()> {
+    singleton(storage, KEY_PAUSE_STATUS).save(pause_status)
+}
+
+// conversions are not paused unless a pause status has been stored
+pub fn read_pause_status(storage: &dyn Storage) -> StdResult<PauseStatus> {
+    Ok(singleton_read(storage, KEY_PAUSE_STATUS)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimit {
+    pub window: Duration,
+    /// maximum anchor token minted per window, unlimited if not set
+    pub mint_limit: Option<Uint128>,
+    /// maximum anchor token redeemed per window, unlimited if not set
+    pub redeem_limit: Option<Uint128>,
+    /// usage of the last window, oldest first
+    pub buckets: Vec<RateLimitBucket>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimitBucket {
+    /// block height or time of the first conversion counted in the bucket
+    pub start: u64,
+    pub minted: Uint128,
+    pub redeemed: Uint128,
+}
+
+pub fn store_rate_limit(storage: &mut dyn Storage, rate_limit: &RateLimit) -> StdResult<()> {
+    singleton(storage, KEY_RATE_LIMIT).save(rate_limit)
+}
+
+pub fn read_rate_limit(storage: &dyn Storage) -> StdResult<Option<RateLimit>> {
+    singleton_read(storage, KEY_RATE_LIMIT).may_load()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenMigration {
+    pub legacy_token: CanonicalAddr,
+    pub new_token: CanonicalAddr,
+    pub new_decimals: u8,
+    /// contract swapping the legacy token for the new token
+    pub migration_contract: CanonicalAddr,
+    /// hook message sent along with the legacy token
+    pub migration_msg: Binary,
+    /// time in seconds after which the migration can be executed
+    pub eta: u64,
+    /// legacy token backing sent to the migration contract
+    pub migrated_amount: Uint128,
+    pub completed: bool,
+}
+
+pub fn store_token_migration(
+    storage: &mut dyn Storage,
+    token_migration: &TokenMigration,
+) -> StdResult<()> {
+    singleton(storage, KEY_TOKEN_MIGRATION).save(token_migration)
+}
+
+pub fn read_token_migration(storage: &dyn Storage) -> StdResult<Option<TokenMigration>> {
+    singleton_read(storage, KEY_TOKEN_MIGRATION).may_load()
+}
+
+pub fn remove_token_migration(storage: &mut dyn Storage) {
+    singleton::<TokenMigration>(storage, KEY_TOKEN_MIGRATION).remove()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Timelock {
+    /// seconds between the proposal and the execution of an owner operation
+    pub delay: u64,
+    pub next_action_id: u64,
+}
+
+pub fn store_timelock(storage: &mut dyn Storage, timelock: &Timelock) -> StdResult<()> {
+    singleton(storage, KEY_TIMELOCK).save(timelock)
+}
+
+pub fn read_timelock(storage: &dyn Storage) -> StdResult<Timelock> {
+    Ok(singleton_read(storage, KEY_TIMELOCK)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedAction {
+    pub msg: ExecuteMsg,
+    /// time in seconds from which the operation can be executed
+    pub eta: u64,
+    /// owner that queued the operation; it only runs while they still own the contract
+    pub proposer: CanonicalAddr,
+}
+
+// This is similar to HashMap<action id, QueuedAction>
+pub fn store_queued_action(
+    storage: &mut dyn Storage,
+    id: u64,
+    action: &QueuedAction,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_QUEUED_ACTIONS).save(&id.to_be_bytes(), action)
+}
+
+pub fn read_queued_action(storage: &dyn Storage, id: u64) -> StdResult<Option<QueuedAction>> {
+    bucket_read(storage, PREFIX_QUEUED_ACTIONS).may_load(&id.to_be_bytes())
+}
+
+pub fn remove_queued_action(storage: &mut dyn Storage, id: u64) {
+    bucket::<QueuedAction>(storage, PREFIX_QUEUED_ACTIONS).remove(&id.to_be_bytes())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stats {
+    pub total_minted: Uint128,
+    pub total_burned: Uint128,
+    pub to_anchor_conversions: u64,
+    pub to_wormhole_conversions: u64,
+}
+
+pub fn store_stats(storage: &mut dyn Storage, stats: &Stats) -> StdResult<()> {
+    singleton(storage, KEY_STATS).save(stats)
+}
+
+pub fn read_stats(storage: &dyn Storage) -> StdResult<Stats> {
+    Ok(singleton_read(storage, KEY_STATS)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct SourceStats {
+    pub total_deposited: Uint128,
+    pub total_returned: Uint128,
+}
+
+pub fn store_source_stats(
+    storage: &mut dyn Storage,
+    token_address: &CanonicalAddr,
+    stats: &SourceStats,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_SOURCE_STATS).save(token_address.as_slice(), stats)
+}
+
+pub fn read_source_stats(
+    storage: &dyn Storage,
+    token_address: &CanonicalAddr,
+) -> StdResult<SourceStats> {
+    Ok(bucket_read(storage, PREFIX_SOURCE_STATS)
+        .may_load(token_address.as_slice())?
+        .unwrap_or_default())
+}
+
+pub fn read_all_source_stats(
+    storage: &dyn Storage,
+) -> StdResult<Vec<(CanonicalAddr, SourceStats)>> {
+    let stats_bucket: ReadonlyBucket<SourceStats> = bucket_read(storage, PREFIX_SOURCE_STATS);
+    stats_bucket
+        .range(None, None, Order::Ascending)
+        .map(|elem| {
+            let (k, v) = elem?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
+}
+
+pub fn store_native_stats(
+    storage: &mut dyn Storage,
+    denom: &str,
+    stats: &SourceStats,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_NATIVE_STATS).save(denom.as_bytes(), stats)
+}
+
+pub fn read_native_stats(storage: &dyn Storage, denom: &str) -> StdResult<SourceStats> {
+    Ok(bucket_read(storage, PREFIX_NATIVE_STATS)
+        .may_load(denom.as_bytes())?
+        .unwrap_or_default())
+}
+
+pub fn read_all_native_stats(storage: &dyn Storage) -> StdResult<Vec<(String, SourceStats)>> {
+    let stats_bucket: ReadonlyBucket<SourceStats> = bucket_read(storage, PREFIX_NATIVE_STATS);
+    stats_bucket
+        .range(None, None, Order::Ascending)
+        .map(|elem| {
+            let (k, v) = elem?;
+            Ok((String::from_utf8(k)?, v))
+        })
+        .collect()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SourceToken {
+    pub decimals: u8,
+    pub enabled: bool,
+    /// amount of the source token deposited and not redeemed yet
+    pub backing: Uint128,
+}
+
+// This is similar to HashMap<source token address, SourceToken>
+pub fn store_source_token(
+    storage: &mut dyn Storage,
+    token_address: &CanonicalAddr,
+    source_token: &SourceToken,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_SOURCE_TOKENS).save(token_address.as_slice(), source_token)
+}
+
+pub fn read_source_token(
+    storage: &dyn Storage,
+    token_address: &CanonicalAddr,
+) -> StdResult<Option<SourceToken>> {
+    bucket_read(storage, PREFIX_SOURCE_TOKENS).may_load(token_address.as_slice())
+}
+
+pub fn remove_source_token(storage: &mut dyn Storage, token_address: &CanonicalAddr) {
+    bucket::<SourceToken>(storage, PREFIX_SOURCE_TOKENS).remove(token_address.as_slice())
+}
+
+pub fn read_all_source_tokens(
+    storage: &dyn Storage,
+) -> StdResult<Vec<(CanonicalAddr, SourceToken)>> {
+    let source_bucket: ReadonlyBucket<SourceToken> = bucket_read(storage, PREFIX_SOURCE_TOKENS);
+    source_bucket
+        .range(None, None, Order::Ascending)
+        .map(|elem| {
+            let (k, v) = elem?;
+            Ok((CanonicalAddr::from(k), v))
+        })
+        .collect()
+}
+
+// This is similar to HashMap<native denom, SourceToken>
+pub fn store_native_source(
+    storage: &mut dyn Storage,
+    denom: &str,
+    native_source: &SourceToken,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_NATIVE_SOURCES).save(denom.as_bytes(), native_source)
+}
+
+pub fn read_native_source(storage: &dyn Storage, denom: &str) -> StdResult<Option<SourceToken>> {
+    bucket_read(storage, PREFIX_NATIVE_SOURCES).may_load(denom.as_bytes())
+}
+
+pub fn remove_native_source(storage: &mut dyn Storage, denom: &str) {
+    bucket::<SourceToken>(storage, PREFIX_NATIVE_SOURCES).remove(denom.as_bytes())
+}
+
+pub fn read_all_native_sources(storage: &dyn Storage) -> StdResult<Vec<(String, SourceToken)>> {
+    let native_bucket: ReadonlyBucket<SourceToken> = bucket_read(storage, PREFIX_NATIVE_SOURCES);
+    native_bucket
+        .range(None, None, Order::Ascending)
+        .map(|elem| {
+            let (k, v) = elem?;
+            Ok((String::from_utf8(k)?, v))
+        })
+        .collect()
+}
+
+/// only allowlisted senders can convert when set
+pub fn store_allowlist_only(storage: &mut dyn Storage, allowlist_only: bool) -> StdResult<()> {
+    singleton(storage, KEY_ALLOWLIST_ONLY).save(&allowlist_only)
+}
+
+pub fn read_allowlist_only(storage: &dyn Storage) -> StdResult<bool> {
+    Ok(singleton_read(storage, KEY_ALLOWLIST_ONLY)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+// This is similar to HashSet<address>, for both the allowlist and the denylist
+pub fn add_to_list(
+    storage: &mut dyn Storage,
+    prefix: &[u8],
+    address: &CanonicalAddr,
+) -> StdResult<()> {
+    bucket(storage, prefix).save(address.as_slice(), &true)
+}
+
+pub fn remove_from_list(storage: &mut dyn Storage, prefix: &[u8], address: &CanonicalAddr) {
+    bucket::<bool>(storage, prefix).remove(address.as_slice())
+}
+
+pub fn is_listed(storage: &dyn Storage, prefix: &[u8], address: &CanonicalAddr) -> StdResult<bool> {
+    Ok(bucket_read::<bool>(storage, prefix)
+        .may_load(address.as_slice())?
+        .is_some())
+}
+
+// settings for pagination
+const MAX_LIMIT: u32 = 30;
+const DEFAULT_LIMIT: u32 = 10;
+pub fn read_source_tokens(
+    deps: Deps,
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<SourceTokenResponse>> {
+    let source_bucket: ReadonlyBucket<SourceToken> =
+        bucket_read(deps.storage, PREFIX_SOURCE_TOKENS);
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    source_bucket
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|elem| {
+            let (k, v) = elem?;
+            let token_address = deps.api.addr_humanize(&CanonicalAddr::from(k))?.to_string();
+            Ok(SourceTokenResponse {
+                token_address,
+                decimals: v.decimals,
+                enabled: v.enabled,
+                backing: v.backing,
+            })
+        })
+        .collect()
+}
+
+pub fn read_list(
+    deps: Deps,
+    prefix: &[u8],
+    start_after: Option<CanonicalAddr>,
+    limit: Option<u32>,
+) -> StdResult<Vec<String>> {
+    let list_bucket: ReadonlyBucket<bool> = bucket_read(deps.storage, prefix);
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = calc_range_start(start_after);
+
+    list_bucket
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|elem| {
+            let (k, _) = elem?;
+            Ok(deps.api.addr_humanize(&CanonicalAddr::from(k))?.to_string())
+        })
+        .collect()
+}
+
+pub fn read_queued_actions(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<QueuedActionResponse>> {
+    let action_bucket: ReadonlyBucket<QueuedAction> = bucket_read(storage, PREFIX_QUEUED_ACTIONS);
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| id.saturating_add(1).to_be_bytes().to_vec());
+
+    action_bucket
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|elem| {
+            let (k, v) = elem?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok(QueuedActionResponse {
+                id: u64::from_be_bytes(id),
+                msg: v.msg,
+                eta: v.eta,
+            })
+        })
+        .collect()
+}
+
+pub fn read_native_sources(
+    storage: &dyn Storage,
+    start_after: Option<String>,
+    limit: Option<u32>,
+) -> StdResult<Vec<NativeSourceResponse>> {
+    let native_bucket: ReadonlyBucket<SourceToken> = bucket_read(storage, PREFIX_NATIVE_SOURCES);
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|denom| {
+        let mut v = denom.into_bytes();
+        v.push(1);
+        v
+    });
+
+    native_bucket
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|elem| {
+            let (k, v) = elem?;
+            Ok(NativeSourceResponse {
+                denom: String::from_utf8(k)?,
+                decimals: v.decimals,
+                enabled: v.enabled,
+                backing: v.backing,
+            })
+        })
+        .collect()
+}
+
+// this will set the first key after the provided key, by appending a 1 byte
+fn calc_range_start(start_after: Option<CanonicalAddr>) -> Option<Vec<u8>> {
+    start_after.map(|addr| {
+        let mut v = addr.as_slice().to_vec();
+        v.push(1);
+        v
+    })
+}
@@ -0,0 +1,89 @@
+use crate::convert::Source;
+use crate::state::{
+    read_all_native_stats, read_all_source_stats, read_native_stats, read_source_stats, read_stats,
+    store_native_stats, store_source_stats, store_stats, SourceStats,
+};
+
+use beth::converter::{SourceStatsResponse, StatsResponse};
+use cosmwasm_std::{Deps, StdResult, Storage, Uint128};
+
+fn update_source_stats(
+    storage: &mut dyn Storage,
+    source_token: &Source,
+    update: impl FnOnce(&mut SourceStats),
+) -> StdResult<()> {
+    match source_token {
+        Source::Token(token_address) => {
+            let mut stats = read_source_stats(storage, token_address)?;
+            update(&mut stats);
+            store_source_stats(storage, token_address, &stats)
+        }
+        Source::Native(denom) => {
+            let mut stats = read_native_stats(storage, denom)?;
+            update(&mut stats);
+            store_native_stats(storage, denom, &stats)
+        }
+    }
+}
+
+pub(crate) fn record_convert_to_anchor(
+    storage: &mut dyn Storage,
+    source_token: &Source,
+    deposited_amount: Uint128,
+    mint_amount: Uint128,
+) -> StdResult<()> {
+    let mut stats = read_stats(storage)?;
+    stats.total_minted += mint_amount;
+    stats.to_anchor_conversions += 1;
+    store_stats(storage, &stats)?;
+
+    update_source_stats(storage, source_token, |source_stats| {
+        source_stats.total_deposited += deposited_amount
+    })
+}
+
+pub(crate) fn record_convert_to_wormhole(
+    storage: &mut dyn Storage,
+    source_token: &Source,
+    return_amount: Uint128,
+    burn_amount: Uint128,
+) -> StdResult<()> {
+    let mut stats = read_stats(storage)?;
+    stats.total_burned += burn_amount;
+    stats.to_wormhole_conversions += 1;
+    store_stats(storage, &stats)?;
+
+    update_source_stats(storage, source_token, |source_stats| {
+        source_stats.total_returned += return_amount
+    })
+}
+
+pub fn query_stats(deps: Deps) -> StdResult<StatsResponse> {
+    let stats = read_stats(deps.storage)?;
+
+    let mut sources = read_all_source_stats(deps.storage)?
+        .into_iter()
+        .map(|(token_address, source_stats)| {
+            Ok(SourceStatsResponse {
+                source: deps.api.addr_humanize(&token_address)?.to_string(),
+                total_deposited: source_stats.total_deposited,
+                total_returned: source_stats.total_returned,
+            })
+        })
+        .collect::<StdResult<Vec<_>>>()?;
+    sources.extend(read_all_native_stats(deps.storage)?.into_iter().map(
+        |(denom, source_stats)| SourceStatsResponse {
+            source: denom,
+            total_deposited: source_stats.total_deposited,
+            total_returned: source_stats.total_returned,
+        },
+    ));
+
+    Ok(StatsResponse {
+        total_minted: stats.total_minted,
+        total_burned: stats.total_burned,
+        to_anchor_conversions: stats.to_anchor_conversions,
+        to_wormhole_conversions: stats.to_wormhole_conversions,
+        sources,
+    })
+}
@@ -1,11 +1,12 @@
 use cosmwasm_std::testing::{MockApi, MockQuerier, MockStorage};
 use cosmwasm_std::{
-    from_slice, to_binary, Api, Coin, ContractResult, OwnedDeps, Querier, QuerierResult,
-    QueryRequest, SystemError, SystemResult, WasmQuery,
+    from_binary, from_slice, to_binary, Api, Coin, ContractResult, Decimal, OwnedDeps, Querier,
+    QuerierResult, QueryRequest, SystemError, SystemResult, Uint128, WasmQuery,
 };
+use std::collections::HashMap;
 
-use cw20::TokenInfoResponse;
-use terra_cosmwasm::TerraQueryWrapper;
+use cw20::{BalanceResponse, Cw20QueryMsg, TokenInfoResponse};
+use terra_cosmwasm::{TaxCapResponse, TaxRateResponse, TerraQuery, TerraQueryWrapper, TerraRoute};
 
 pub const MOCK_CONTRACT_ADDR: &str = "cosmos2contract";
 
@@ -29,6 +30,10 @@ pub struct WasmMockQuerier {
     base: MockQuerier<TerraQueryWrapper>,
     // first one is anchor token decimals, the second one is wormhole token decimals
     decimals: (u8, u8),
+    // decimals of additional source tokens
+    source_decimals: HashMap<String, u8>,
+    token_balances: HashMap<String, HashMap<String, Uint128>>,
+    token_supplies: HashMap<String, Uint128>,
 }
 
 impl Querier for WasmMockQuerier {
@@ -50,24 +55,64 @@ impl Querier for WasmMockQuerier {
 impl WasmMockQuerier {
     pub fn handle_query(&self, request: &QueryRequest<TerraQueryWrapper>) -> QuerierResult {
         match &request {
-            QueryRequest::Wasm(WasmQuery::Smart {
-                contract_addr,
-                msg: _,
-            }) => {
-                if contract_addr == "wormhole_token0000" {
-                    SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
-                        name: "wormhole_token".to_string(),
-                        symbol: "WORM".to_string(),
-                        decimals: self.decimals.1,
-                        total_supply: Default::default(),
-                    })))
+            QueryRequest::Custom(TerraQueryWrapper { route, query_data }) => {
+                if &TerraRoute::Treasury == route {
+                    match query_data {
+                        TerraQuery::TaxRate {} => {
+                            let res = TaxRateResponse {
+                                rate: Decimal::percent(1),
+                            };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        TerraQuery::TaxCap { denom: _ } => {
+                            let cap = Uint128::new(1000000u128);
+                            let res = TaxCapResponse { cap };
+                            SystemResult::Ok(ContractResult::from(to_binary(&res)))
+                        }
+                        _ => panic!("DO NOT ENTER HERE"),
+                    }
                 } else {
-                    SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
-                        name: "anchor_token".to_string(),
-                        symbol: "ANC".to_string(),
-                        decimals: self.decimals.0,
-                        total_supply: Default::default(),
-                    })))
+                    panic!("DO NOT ENTER HERE")
+                }
+            }
+            QueryRequest::Wasm(WasmQuery::Smart { contract_addr, msg }) => {
+                match from_binary(msg).unwrap() {
+                    Cw20QueryMsg::TokenInfo {} => {
+                        if let Some(decimals) = self.source_decimals.get(contract_addr) {
+                            SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                                name: "source_token".to_string(),
+                                symbol: "SRC".to_string(),
+                                decimals: *decimals,
+                                total_supply: self.total_supply(contract_addr),
+                            })))
+                        } else if contract_addr == "wormhole_token0000" {
+                            SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                                name: "wormhole_token".to_string(),
+                                symbol: "WORM".to_string(),
+                                decimals: self.decimals.1,
+                                total_supply: self.total_supply(contract_addr),
+                            })))
+                        } else {
+                            SystemResult::Ok(ContractResult::from(to_binary(&TokenInfoResponse {
+                                name: "anchor_token".to_string(),
+                                symbol: "ANC".to_string(),
+                                decimals: self.decimals.0,
+                                total_supply: self.total_supply(contract_addr),
+                            })))
+                        }
+                    }
+                    Cw20QueryMsg::Balance { address } => {
+                        let balance = self
+                            .token_balances
+                            .get(contract_addr)
+                            .and_then(|balances| balances.get(&address))
+                            .copied()
+                            .unwrap_or_default();
+                        SystemResult::Ok(ContractResult::from(to_binary(&BalanceResponse {
+                            balance,
+                        })))
+                    }
+                    _ => panic!("DO NOT ENTER HERE"),
                 }
             }
             _ => self.base.handle_query(request),
@@ -80,10 +125,47 @@ impl WasmMockQuerier {
         WasmMockQuerier {
             base,
             decimals: (6, 8),
+            source_decimals: HashMap::new(),
+            token_balances: HashMap::new(),
+            token_supplies: HashMap::new(),
         }
     }
 
+    fn total_supply(&self, contract_addr: &str) -> Uint128 {
+        self.token_supplies
+            .get(contract_addr)
+            .copied()
+            .unwrap_or_default()
+    }
+
     pub fn set_decimals(&mut self, anchor_decimals: u8, wormhole_decimals: u8) {
         self.decimals = (anchor_decimals, wormhole_decimals)
     }
+
+    pub fn with_source_decimals(&mut self, source_decimals: &[(&str, u8)]) {
+        for (token, decimals) in source_decimals.iter() {
+            self.source_decimals.insert(token.to_string(), *decimals);
+        }
+    }
+
+    pub fn with_native_balance(&mut self, addr: &str, balance: Vec<Coin>) {
+        self.base.update_balance(addr, balance);
+    }
+
+    pub fn with_token_supplies(&mut self, supplies: &[(&str, &Uint128)]) {
+        for (token, supply) in supplies.iter() {
+            self.token_supplies.insert(token.to_string(), **supply);
+        }
+    }
+
+    pub fn with_token_balances(&mut self, balances: &[(&str, &[(&str, &Uint128)])]) {
+        for (token, token_balances) in balances.iter() {
+            let mut contract_balances = HashMap::new();
+            for (addr, balance) in token_balances.iter() {
+                contract_balances.insert(addr.to_string(), **balance);
+            }
+            self.token_balances
+                .insert(token.to_string(), contract_balances);
+        }
+    }
 }
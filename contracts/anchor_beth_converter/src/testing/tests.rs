@@ -1,18 +1,61 @@
-use cosmwasm_std::testing::{mock_env, mock_info};
+use crate::error::ContractError;
+use crate::timelock::MAX_TIMELOCK_DELAY;
+use crate::token_migration::TOKEN_MIGRATION_DELAY;
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage};
 use cosmwasm_std::{
-    from_binary, to_binary, Attribute, CosmosMsg, StdError, SubMsg, Uint128, WasmMsg,
+    from_binary, to_binary, Api, Attribute, BankMsg, Binary, Coin, CosmosMsg, OwnedDeps, Storage,
+    SubMsg, Uint128, WasmMsg,
 };
+use cosmwasm_storage::to_length_prefixed;
 
-use crate::contract::{execute, instantiate, query};
-use crate::testing::mock_querier::mock_dependencies;
-use beth::converter::Cw20HookMsg::{ConvertAnchorToWormhole, ConvertWormholeToAnchor};
-use beth::converter::ExecuteMsg::{Receive, RegisterTokens};
-use beth::converter::{ConfigResponse, InstantiateMsg, QueryMsg};
+use crate::contract::{execute, instantiate, migrate, query};
+use crate::migration::{CONTRACT_NAME, CONTRACT_VERSION};
+use crate::state::KEY_CONFIG;
+use crate::testing::mock_querier::{mock_dependencies, WasmMockQuerier, MOCK_CONTRACT_ADDR};
+use beth::converter::Cw20HookMsg::{
+    ConvertAnchorToNative, ConvertAnchorToWormhole, ConvertWormholeToAnchor,
+};
+use beth::converter::ExecuteMsg::{
+    AcceptOwnership, AddNativeSource, AddSourceToken, CancelAction, CancelOwnershipTransfer,
+    CancelTokenMigration, ConvertFrom, ConvertNativeToAnchor, ExecuteAction, ExecuteTokenMigration,
+    FinalizeTokenMigration, Pause, ProposeAction, ProposeNewOwner, ProposeTokenMigration, Receive,
+    RecoverToken, RegisterTokens, RemoveNativeSource, RemoveSourceToken, SetAllowlistOnly,
+    SetGuardian, SetTimelockDelay, SweepSurplus, Unpause, UpdateAllowlist, UpdateDenylist,
+    UpdateFees, UpdateNativeSource, UpdateRateLimit, UpdateSourceToken,
+};
+use beth::converter::{
+    AllowlistResponse, BackingResponse, ConfigResponse, ConvertDirection, Cw20HookMsg,
+    DenylistResponse, Forward, InstantiateMsg, MigrateMsg, MigrationParams, NativeSourceResponse,
+    NativeSourcesResponse, PauseStatusResponse, PendingOwnerResponse, QueryMsg,
+    QueuedActionResponse, QueuedActionsResponse, RateLimitResponse, SimulationResponse,
+    SourceStatsResponse, SourceTokenResponse, SourceTokensResponse, StatsResponse,
+    TokenMigrationResponse, TokenMigrationStatus,
+};
+use cw0::Duration;
+use cw2::{get_contract_version, set_contract_version};
 use cw20::{Cw20ExecuteMsg, Cw20ReceiveMsg};
 
 const MOCK_OWNER_ADDR: &str = "owner0000";
 const MOCK_ANCHOR_TOKEN_CONTRACT_ADDR: &str = "beth_token0000";
 const MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR: &str = "wormhole_token0000";
+const MOCK_SOURCE_TOKEN_CONTRACT_ADDR: &str = "source_token0000";
+const MOCK_FEE_COLLECTOR_ADDR: &str = "fee_collector0000";
+const MOCK_GUARDIAN_ADDR: &str = "guardian0000";
+
+// the converter holds what has been deposited through the source token
+fn hold_backing(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, token: &str) {
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: token.to_string(),
+        },
+    )
+    .unwrap();
+    let source: SourceTokenResponse = from_binary(&res).unwrap();
+    deps.querier
+        .with_token_balances(&[(token, &[(MOCK_CONTRACT_ADDR, &source.backing)])]);
+}
 
 fn default_init() -> InstantiateMsg {
     InstantiateMsg {
@@ -38,6 +81,11 @@ fn proper_init() {
             owner: MOCK_OWNER_ADDR.to_string(),
             anchor_token_address: None,
             wormhole_token_address: None,
+            anchor_decimals: None,
+            wormhole_decimals: None,
+            fee_collector: None,
+            to_anchor_fee_bps: 0,
+            to_wormhole_fee_bps: 0,
         }
     );
 }
@@ -76,14 +124,18 @@ fn proper_convert_to_anchor() {
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_binary(&ConvertWormholeToAnchor {}).unwrap(),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
     let invalid_info = mock_info("invalid", &[]);
     let error_res =
         execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
     // successful request
     let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
@@ -107,21 +159,27 @@ fn proper_convert_to_anchor() {
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(1),
-        msg: to_binary(&ConvertWormholeToAnchor {}).unwrap(),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
     let invalid_info = mock_info("invalid", &[]);
     let error_res =
         execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
     // successful request
     let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
     let res = execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap_err();
     assert_eq!(
         res,
-        StdError::generic_err("cannot convert; conversion is only possible for amounts greater than 100 wormhole token")
+        ContractError::AmountTooSmall {
+            min: Uint128::new(100)
+        }
     );
 }
 
@@ -156,20 +214,39 @@ fn proper_convert_to_wormhole() {
         Attribute::new("action", "register_token_contracts")
     );
 
+    // deposit the wormhole tokens backing the redemption
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_binary(&ConvertAnchorToWormhole {}).unwrap(),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
     let invalid_info = mock_info("invalid", &[]);
     let error_res =
         execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
     // successful
     let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
     let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
     assert_eq!(res.messages.len(), 2);
     assert_eq!(
@@ -232,14 +309,18 @@ fn proper_convert_to_anchor_with_more_decimals() {
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_binary(&ConvertWormholeToAnchor {}).unwrap(),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
     let invalid_info = mock_info("invalid", &[]);
     let error_res =
         execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
     // successful request
     let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
@@ -292,20 +373,39 @@ fn proper_convert_to_wormhole_with_less_decimals() {
         Attribute::new("action", "register_token_contracts")
     );
 
+    // deposit the wormhole tokens backing the redemption
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(100000000),
-        msg: to_binary(&ConvertAnchorToWormhole {}).unwrap(),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // unauthorized request
     let invalid_info = mock_info("invalid", &[]);
     let error_res =
         execute(deps.as_mut(), mock_env(), invalid_info, receive_msg.clone()).unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
     // successful
     let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
     let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
     assert_eq!(res.messages.len(), 2);
     assert_eq!(
@@ -338,7 +438,12 @@ fn proper_convert_to_wormhole_with_less_decimals() {
     let receive_msg = Receive(Cw20ReceiveMsg {
         sender: sender.to_string(),
         amount: Uint128::new(1),
-        msg: to_binary(&ConvertAnchorToWormhole {}).unwrap(),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
     });
 
     // successful request
@@ -346,53 +451,3239 @@ fn proper_convert_to_wormhole_with_less_decimals() {
     let res = execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap_err();
     assert_eq!(
         res,
-        StdError::generic_err(
-            "cannot convert; conversion is only possible for amounts greater than 100 anchor token"
-        )
+        ContractError::AmountTooSmall {
+            min: Uint128::new(100)
+        }
     );
 }
 
 #[test]
-fn proper_update_config() {
+fn proper_convert_to_wormhole_refunds_dust() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(sender, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+
+    // set anchor and wormhole decimals
+    deps.querier.set_decimals(10, 8);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        update_config,
+    )
+    .unwrap();
+
+    // deposit the wormhole tokens backing the redemption
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
+    // 50 anchor token cannot be represented in wormhole decimals
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000050),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(1000000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(100000000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(50)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(res.attributes[5], Attribute::new("refund_amount", "50"));
+}
+
+#[test]
+fn proper_convert_with_fees() {
     let mut deps = mock_dependencies(&[]);
     let init_msg = default_init();
 
+    let sender = "addr0000";
     let info = mock_info(MOCK_OWNER_ADDR, &[]);
-    let invalid_info = mock_info("invalid", &[]);
 
-    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
-    assert_eq!(0, res.messages.len());
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
 
     let update_config = RegisterTokens {
         anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
         wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
     };
 
+    // set anchor and wormhole decimals
+    deps.querier.set_decimals(6, 8);
+
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
     // unauthorized request
+    let update_fees = UpdateFees {
+        fee_collector: Some(MOCK_FEE_COLLECTOR_ADDR.to_string()),
+        to_anchor_fee_bps: Some(30),
+        to_wormhole_fee_bps: Some(10),
+    };
     let error_res = execute(
         deps.as_mut(),
         mock_env(),
-        invalid_info,
-        update_config.clone(),
+        mock_info("invalid", &[]),
+        update_fees.clone(),
     )
     .unwrap_err();
-    assert_eq!(error_res, StdError::generic_err("unauthorized"));
+    assert_eq!(error_res, ContractError::Unauthorized {});
 
-    //successful one
-    let res = execute(deps.as_mut(), mock_env(), info, update_config).unwrap();
-    assert_eq!(
-        res.attributes[0],
-        Attribute::new("action", "register_token_contracts")
-    );
+    // fee above the cap
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        UpdateFees {
+            fee_collector: Some(MOCK_FEE_COLLECTOR_ADDR.to_string()),
+            to_anchor_fee_bps: Some(101),
+            to_wormhole_fee_bps: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::FeeTooHigh { max_bps: 100 });
+
+    // fee without a collector
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        UpdateFees {
+            fee_collector: None,
+            to_anchor_fee_bps: Some(30),
+            to_wormhole_fee_bps: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::FeeCollectorNotSet {});
+
+    // successful one
+    let res = execute(deps.as_mut(), mock_env(), info, update_fees).unwrap();
+    assert_eq!(res.attributes[0], Attribute::new("action", "update_fees"));
 
     let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
     let config_response: ConfigResponse = from_binary(&res).unwrap();
     assert_eq!(
-        config_response,
-        ConfigResponse {
-            owner: MOCK_OWNER_ADDR.to_string(),
-            anchor_token_address: Some("beth_token0000".to_string()),
-            wormhole_token_address: Some("wormhole_token0000".to_string()),
+        config_response.fee_collector,
+        Some(MOCK_FEE_COLLECTOR_ADDR.to_string())
+    );
+    assert_eq!(config_response.to_anchor_fee_bps, 30);
+    assert_eq!(config_response.to_wormhole_fee_bps, 10);
+
+    // 0.3% of the wormhole token goes to the fee collector
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+    assert_eq!(res.messages.len(), 2);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: sender.to_string(),
+                // (100000000 - 300000) / 10^2 = 997000
+                amount: Uint128::new(997000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: MOCK_FEE_COLLECTOR_ADDR.to_string(),
+                amount: Uint128::new(300000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(res.attributes[4], Attribute::new("fee_amount", "300000"));
+
+    // 0.1% of the anchor token goes to the fee collector
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(997000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+    assert_eq!(res.messages.len(), 3);
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                // (997000 - 997) * 10^2 = 99600300
+                amount: Uint128::new(99600300)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Burn {
+                amount: Uint128::new(996003)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.messages[2],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: MOCK_FEE_COLLECTOR_ADDR.to_string(),
+                amount: Uint128::new(997)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(res.attributes[6], Attribute::new("fee_amount", "997"));
+}
+
+#[test]
+fn proper_pause() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let guardian_info = mock_info(MOCK_GUARDIAN_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
+    // only the owner can set the guardian
+    let set_guardian = SetGuardian {
+        guardian: Some(MOCK_GUARDIAN_ADDR.to_string()),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        guardian_info.clone(),
+        set_guardian.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), info.clone(), set_guardian).unwrap();
+
+    // unauthorized request
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        Pause {
+            mint: true,
+            redeem: true,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    // the guardian pauses minting
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        guardian_info.clone(),
+        Pause {
+            mint: true,
+            redeem: false,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "pause"),
+            Attribute::new("mint_paused", "true"),
+            Attribute::new("redeem_paused", "false"),
+        ]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PauseStatus {}).unwrap();
+    let pause_status: PauseStatusResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pause_status,
+        PauseStatusResponse {
+            guardian: Some(MOCK_GUARDIAN_ADDR.to_string()),
+            mint_paused: true,
+            redeem_paused: false,
         }
     );
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::MintPaused {});
+
+    // the guardian cannot unpause
+    let unpause = Unpause {
+        mint: true,
+        redeem: true,
+    };
+    let error_res = execute(deps.as_mut(), mock_env(), guardian_info, unpause.clone()).unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    // the owner pauses redemption and unpauses everything afterwards
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        Pause {
+            mint: false,
+            redeem: true,
+        },
+    )
+    .unwrap();
+
+    let redeem_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        beth_info.clone(),
+        redeem_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::RedeemPaused {});
+
+    let res = execute(deps.as_mut(), mock_env(), info, unpause).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "unpause"),
+            Attribute::new("mint_paused", "false"),
+            Attribute::new("redeem_paused", "false"),
+        ]
+    );
+
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    execute(deps.as_mut(), mock_env(), beth_info, redeem_msg).unwrap();
+}
+
+#[test]
+fn proper_rate_limit() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+
+    // set anchor and wormhole decimals
+    deps.querier.set_decimals(6, 8);
+
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
+    let update_rate_limit = UpdateRateLimit {
+        window: Duration::Height(100),
+        mint_limit: Some(Uint128::new(1500000)),
+        redeem_limit: Some(Uint128::new(500000)),
+    };
+
+    // unauthorized request
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        update_rate_limit.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), mock_env(), info, update_rate_limit).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute::new("action", "update_rate_limit")
+    );
+
+    // mint 1000000 out of 1500000
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap();
+
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::MintRateLimitExceeded {
+            remaining: Uint128::new(500000)
+        }
+    );
+
+    // redeem 600000 out of 500000
+    let redeem_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(600000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let error_res = execute(deps.as_mut(), mock_env(), beth_info, redeem_msg).unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::RedeemRateLimitExceeded {
+            remaining: Uint128::new(500000)
+        }
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::RateLimit {}).unwrap();
+    let rate_limit: RateLimitResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        rate_limit,
+        RateLimitResponse {
+            window: Some(Duration::Height(100)),
+            window_start: Some(mock_env().block.height),
+            mint_limit: Some(Uint128::new(1500000)),
+            redeem_limit: Some(Uint128::new(500000)),
+            minted: Uint128::new(1000000),
+            redeemed: Uint128::zero(),
+            remaining_mint: Some(Uint128::new(500000)),
+            remaining_redeem: Some(Uint128::new(500000)),
+        }
+    );
+
+    // mint the remaining 500000 later in the window
+    let mut env = mock_env();
+    env.block.height += 60;
+    let half_receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(50000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        wormhole_info.clone(),
+        half_receive_msg,
+    )
+    .unwrap();
+
+    // after 100 blocks only the first mint has slid out of the window
+    env.block.height += 40;
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::RateLimit {}).unwrap();
+    let rate_limit: RateLimitResponse = from_binary(&res).unwrap();
+    assert_eq!(rate_limit.window_start, Some(mock_env().block.height + 60));
+    assert_eq!(rate_limit.minted, Uint128::new(500000));
+    assert_eq!(rate_limit.remaining_mint, Some(Uint128::new(1000000)));
+
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        wormhole_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap();
+
+    // crossing the end of the first window does not reset the usage
+    let error_res = execute(
+        deps.as_mut(),
+        env.clone(),
+        wormhole_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::MintRateLimitExceeded {
+            remaining: Uint128::zero()
+        }
+    );
+
+    // the second mint leaves the window 100 blocks after it happened
+    env.block.height += 60;
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::RateLimit {}).unwrap();
+    let rate_limit: RateLimitResponse = from_binary(&res).unwrap();
+    assert_eq!(rate_limit.minted, Uint128::new(1000000));
+    assert_eq!(rate_limit.remaining_mint, Some(Uint128::new(500000)));
+}
+
+#[test]
+fn proper_simulate() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+
+    // set anchor and wormhole decimals
+    deps.querier.set_decimals(6, 8);
+
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
+    let update_fees = UpdateFees {
+        fee_collector: Some(MOCK_FEE_COLLECTOR_ADDR.to_string()),
+        to_anchor_fee_bps: Some(30),
+        to_wormhole_fee_bps: Some(10),
+    };
+    execute(deps.as_mut(), mock_env(), info, update_fees).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToAnchor {
+            source_token: None,
+            amount: Uint128::new(100000000),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(997000),
+            refund_amount: Uint128::zero(),
+            fee_amount: Uint128::new(300000),
+            min_amount: Uint128::new(100),
+        }
+    );
+
+    // the dust below one anchor unit is refunded to the sender
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToAnchor {
+            source_token: None,
+            amount: Uint128::new(100000099),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(997000),
+            refund_amount: Uint128::new(99),
+            fee_amount: Uint128::new(300000),
+            min_amount: Uint128::new(100),
+        }
+    );
+
+    // the simulation fails the same way as the conversion
+    let error_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToAnchor {
+            source_token: None,
+            amount: Uint128::new(99),
+        },
+    )
+    .unwrap_err();
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(99),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    assert_eq!(
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            wormhole_info.clone(),
+            receive_msg
+        )
+        .unwrap_err(),
+        error_res
+    );
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg,
+    )
+    .unwrap();
+    assert_eq!(res.attributes[3], Attribute::new("minted_amount", "997000"));
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000099),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+    assert_eq!(res.attributes[3], Attribute::new("minted_amount", "997000"));
+    assert_eq!(res.attributes[5], Attribute::new("refund_amount", "99"));
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(99),
+            })
+            .unwrap(),
+            funds: vec![],
+        }))
+    );
+
+    // the refunded dust does not back the minted anchor token
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let source_token: SourceTokenResponse = from_binary(&res).unwrap();
+    assert_eq!(source_token.backing, Uint128::new(199400000));
+
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToWormhole {
+            source_token: None,
+            amount: Uint128::new(997000),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(99600300),
+            refund_amount: Uint128::zero(),
+            fee_amount: Uint128::new(997),
+            min_amount: Uint128::new(1),
+        }
+    );
+
+    // more than the backing
+    let error_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToWormhole {
+            source_token: None,
+            amount: Uint128::new(2000000),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::InsufficientBacking {
+            backing: Uint128::new(199400000)
+        }
+    );
+
+    // native coins are simulated net of tax, as they are paid out
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        AddNativeSource {
+            denom: "uweth".to_string(),
+            decimals: 8,
+        },
+    )
+    .unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToAnchor {
+            source_token: Some("uweth".to_string()),
+            amount: Uint128::new(100000099),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(997000),
+            refund_amount: Uint128::new(98),
+            fee_amount: Uint128::new(300000),
+            min_amount: Uint128::new(100),
+        }
+    );
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(100000099, "uweth")]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages[1],
+        SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: sender.to_string(),
+            amount: vec![Coin::new(98, "uweth")],
+        }))
+    );
+
+    deps.querier
+        .with_native_balance(MOCK_CONTRACT_ADDR, vec![Coin::new(99700000, "uweth")]);
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToWormhole {
+            source_token: Some("uweth".to_string()),
+            amount: Uint128::new(997000),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        simulation,
+        SimulationResponse {
+            return_amount: Uint128::new(98614158),
+            refund_amount: Uint128::zero(),
+            fee_amount: Uint128::new(997),
+            min_amount: Uint128::new(1),
+        }
+    );
+}
+
+#[test]
+fn proper_backing() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(sender, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+    // backing can only be computed once the tokens are registered
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap_err();
+    assert_eq!(err, ContractError::TokensNotRegistered {});
+
+    deps.querier.set_decimals(6, 8);
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
+    // the converter holds a little more wormhole token than the minted supply
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(10000000050))],
+    )]);
+    deps.querier
+        .with_token_supplies(&[(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &Uint128::new(100000000))]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        backing,
+        BackingResponse {
+            decimals: 8,
+            wormhole_balance: Uint128::new(10000000050),
+            anchor_supply: Uint128::new(10000000000),
+            is_backed: true,
+            surplus: Uint128::new(50),
+        }
+    );
+
+    // the converter lost part of its wormhole token
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(9000000000))],
+    )]);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert!(!backing.is_backed);
+    assert_eq!(backing.surplus, Uint128::zero());
+
+    // redemptions are refused while anchor token is not fully backed
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        beth_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Undercollateralized {});
+
+    // once the balance is restored redemptions go through
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(10000000000))],
+    )]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        beth_info.clone(),
+        receive_msg.clone(),
+    )
+    .unwrap();
+
+    // a source which lost its deposits only blocks its own redemptions
+    deps.querier
+        .with_source_decimals(&[(MOCK_SOURCE_TOKEN_CONTRACT_ADDR, 18)]);
+    let add_source = AddSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        add_source,
+    )
+    .unwrap();
+    let deposit_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000000000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let source_info = mock_info(MOCK_SOURCE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), source_info, deposit_msg).unwrap();
+
+    let redeem_source_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: Some(MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string()),
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        beth_info.clone(),
+        redeem_source_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Undercollateralized {});
+
+    execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+}
+
+#[test]
+fn proper_sweep_and_recover() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(sender, &[]);
+    instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
+    deps.querier.with_token_balances(&[
+        (
+            MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+            &[(MOCK_CONTRACT_ADDR, &Uint128::new(10000000050))],
+        ),
+        (
+            "other_token0000",
+            &[(MOCK_CONTRACT_ADDR, &Uint128::new(1234))],
+        ),
+    ]);
+    deps.querier
+        .with_token_supplies(&[(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &Uint128::new(100000000))]);
+
+    // only the owner can sweep or recover
+    let sweep = SweepSurplus {
+        recipient: "treasury0000".to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        sweep.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), owner_info.clone(), sweep.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury0000".to_string(),
+                amount: Uint128::new(50),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    // tokens deposited for the outstanding supply are never swept,
+    // even if part of the supply has been burnt elsewhere
+    deps.querier
+        .with_token_supplies(&[(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &Uint128::new(99000000))]);
+    let res = execute(deps.as_mut(), mock_env(), owner_info.clone(), sweep.clone()).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "treasury0000".to_string(),
+                amount: Uint128::new(50),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(10000000000))],
+    )]);
+    let err = execute(deps.as_mut(), mock_env(), owner_info.clone(), sweep).unwrap_err();
+    assert_eq!(err, ContractError::NoSurplus {});
+
+    // source tokens cannot be recovered
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RecoverToken {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            recipient: sender.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CannotRecoverSource {
+            token: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        }
+    );
+
+    let recover = RecoverToken {
+        token_address: "other_token0000".to_string(),
+        recipient: sender.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        recover.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        recover.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: "other_token0000".to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(1234),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    deps.querier
+        .with_token_balances(&[("other_token0000", &[(MOCK_CONTRACT_ADDR, &Uint128::zero())])]);
+    let err = execute(deps.as_mut(), mock_env(), owner_info, recover).unwrap_err();
+    assert_eq!(err, ContractError::NothingToRecover {});
+}
+
+#[test]
+fn proper_token_migration() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    instantiate(deps.as_mut(), mock_env(), mock_info(sender, &[]), init_msg).unwrap();
+
+    let new_token = "new_wormhole_token0000";
+    let migration_contract = "migrator0000";
+    deps.querier.set_decimals(6, 8);
+    deps.querier.with_source_decimals(&[(new_token, 8)]);
+
+    let propose_msg = ProposeTokenMigration {
+        new_token_address: new_token.to_string(),
+        migration_contract: migration_contract.to_string(),
+        migration_msg: to_binary(&"migrate").unwrap(),
+    };
+
+    // tokens must be registered first
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        propose_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TokensNotRegistered {});
+
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+
+    // unauthorized
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        propose_msg.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoPendingTokenMigration {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        propose_msg.clone(),
+    )
+    .unwrap();
+
+    let eta = mock_env().block.time.seconds() + TOKEN_MIGRATION_DELAY;
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::TokenMigration {}).unwrap();
+    let token_migration: TokenMigrationResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        token_migration,
+        TokenMigrationResponse {
+            legacy_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            new_token_address: new_token.to_string(),
+            migration_contract: migration_contract.to_string(),
+            migration_msg: to_binary(&"migrate").unwrap(),
+            eta,
+            migrated_amount: Uint128::zero(),
+            status: TokenMigrationStatus::Pending,
+        }
+    );
+
+    // only one migration can be pending
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        propose_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TokenMigrationAlreadyPending {});
+
+    // timelocked
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::TokenMigrationTimelocked { eta });
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(TOKEN_MIGRATION_DELAY);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteTokenMigration {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: migration_contract.to_string(),
+                    amount: Uint128::new(10000000000),
+                    msg: to_binary(&"migrate").unwrap(),
+                })
+                .unwrap(),
+                funds: vec![]
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&FinalizeTokenMigration {}).unwrap(),
+                funds: vec![]
+            })),
+        ]
+    );
+
+    // only the converter can finalize
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        FinalizeTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the migration contract did not return enough new token
+    deps.querier.with_token_balances(&[(
+        new_token,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(9999999999))],
+    )]);
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        FinalizeTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TokenMigrationShortfall {
+            balance: Uint128::new(9999999999),
+            expected: Uint128::new(10000000000)
+        }
+    );
+
+    deps.querier.with_token_balances(&[(
+        new_token,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(10000000000))],
+    )]);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        FinalizeTokenMigration {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::TokenMigration {}).unwrap();
+    let token_migration: TokenMigrationResponse = from_binary(&res).unwrap();
+    assert_eq!(token_migration.status, TokenMigrationStatus::Completed);
+    assert_eq!(token_migration.migrated_amount, Uint128::new(10000000000));
+
+    // the new token replaced the legacy token as the source for redemptions
+    let res = query(deps.as_ref(), env.clone(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.wormhole_token_address, Some(new_token.to_string()));
+
+    let res = query(
+        deps.as_ref(),
+        env.clone(),
+        QueryMsg::SourceTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let source_tokens: SourceTokensResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        source_tokens.source_tokens,
+        vec![SourceTokenResponse {
+            token_address: new_token.to_string(),
+            decimals: 8,
+            enabled: true,
+            backing: Uint128::new(10000000000),
+        }]
+    );
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), env, beth_info, receive_msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: new_token.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(10000000000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+}
+
+#[test]
+fn proper_cancel_token_migration() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let new_token = "new_wormhole_token0000";
+    deps.querier.with_source_decimals(&[(new_token, 8)]);
+
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    let propose_msg = ProposeTokenMigration {
+        new_token_address: new_token.to_string(),
+        migration_contract: "migrator0000".to_string(),
+        migration_msg: to_binary(&"migrate").unwrap(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        propose_msg,
+    )
+    .unwrap();
+
+    // unauthorized
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        CancelTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        CancelTokenMigration {},
+    )
+    .unwrap();
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::TokenMigration {}).unwrap_err();
+    assert_eq!(err, ContractError::NoTokenMigration {});
+
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(TOKEN_MIGRATION_DELAY);
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteTokenMigration {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoPendingTokenMigration {});
+}
+
+#[test]
+fn proper_ownership_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let new_owner = "new_owner0000";
+
+    // only the owner can propose a new owner
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: Some(u64::MAX),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidOwnershipExpiry {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NoPendingOwner {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: Some(100),
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pending_owner,
+        PendingOwnerResponse {
+            pending_owner: Some(new_owner.to_string()),
+            expires_at: Some(mock_env().block.time.seconds() + 100),
+        }
+    );
+
+    // only the proposed owner can accept
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    // the proposal expires
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(new_owner, &[]),
+        AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::OwnershipProposalExpired {});
+
+    // the owner can cancel the proposal
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        CancelOwnershipTransfer {},
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        CancelOwnershipTransfer {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pending_owner,
+        PendingOwnerResponse {
+            pending_owner: None,
+            expires_at: None,
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        AcceptOwnership {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.owner, new_owner.to_string());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(pending_owner.pending_owner, None);
+
+    // the previous owner lost its rights
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ProposeNewOwner {
+            new_owner: MOCK_OWNER_ADDR.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+}
+
+#[test]
+fn proper_native_source() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    instantiate(deps.as_mut(), mock_env(), mock_info(sender, &[]), init_msg).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    let add_native = AddNativeSource {
+        denom: "uweth".to_string(),
+        decimals: 8,
+    };
+
+    // unauthorized
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        add_native.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        add_native.clone(),
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        add_native,
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SourceAlreadyRegistered {
+            token: "uweth".to_string()
+        }
+    );
+
+    // exactly one coin must be sent
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::InvalidNativeFunds {});
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(10000000000, "uluna")]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SourceNotRegistered {
+            token: "uluna".to_string()
+        }
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(10000000000, "uweth")]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: sender.to_string(),
+                amount: Uint128::new(100000000),
+            })
+            .unwrap(),
+            funds: vec![]
+        }))]
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NativeSources {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let native_sources: NativeSourcesResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        native_sources.native_sources,
+        vec![NativeSourceResponse {
+            denom: "uweth".to_string(),
+            decimals: 8,
+            enabled: true,
+            backing: Uint128::new(10000000000),
+        }]
+    );
+
+    // the native balance counts towards the backing
+    deps.querier
+        .with_native_balance(MOCK_CONTRACT_ADDR, vec![Coin::new(10000000000, "uweth")]);
+    deps.querier
+        .with_token_supplies(&[(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &Uint128::new(100000000))]);
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Backing {}).unwrap();
+    let backing: BackingResponse = from_binary(&res).unwrap();
+    assert_eq!(backing.wormhole_balance, Uint128::new(10000000000));
+    assert!(backing.is_backed);
+
+    // the native source cannot be removed while it backs anchor token
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RemoveNativeSource {
+            denom: "uweth".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SourceStillBacking {
+            backing: Uint128::new(10000000000)
+        }
+    );
+
+    // disabled native sources cannot be deposited, but can still be redeemed
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        UpdateNativeSource {
+            denom: "uweth".to_string(),
+            enabled: false,
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(10000000000, "uweth")]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SourceDisabled {
+            token: "uweth".to_string()
+        }
+    );
+
+    // the tax is deducted from the returned coins
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SimulateConvertToWormhole {
+            source_token: Some("uweth".to_string()),
+            amount: Uint128::new(100000000),
+        },
+    )
+    .unwrap();
+    let simulation: SimulationResponse = from_binary(&res).unwrap();
+    assert_eq!(simulation.return_amount, Uint128::new(9999000000));
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertAnchorToNative {
+            denom: "uweth".to_string(),
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]),
+        receive_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: sender.to_string(),
+                // tax is capped at 1000000
+                amount: vec![Coin::new(9999000000, "uweth")],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Burn {
+                    amount: Uint128::new(100000000)
+                })
+                .unwrap(),
+                funds: vec![]
+            })),
+        ]
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RemoveNativeSource {
+            denom: "uweth".to_string(),
+        },
+    )
+    .unwrap();
+    let err = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::NativeSource {
+            denom: "uweth".to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SourceNotRegistered {
+            token: "uweth".to_string()
+        }
+    );
+}
+
+#[test]
+fn proper_convert_and_forward() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let market = "market0000";
+    let bridge = "bridge0000";
+    instantiate(deps.as_mut(), mock_env(), mock_info(sender, &[]), init_msg).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    let register = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        register,
+    )
+    .unwrap();
+
+    // hooks without recipient and forward keep working
+    let hook: Cw20HookMsg = from_binary(&Binary::from(
+        br#"{"convert_wormhole_to_anchor":{}}"#.to_vec(),
+    ))
+    .unwrap();
+    assert_eq!(
+        hook,
+        ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None
+        }
+    );
+
+    let forward = Forward {
+        contract: market.to_string(),
+        msg: to_binary(&"deposit_collateral").unwrap(),
+    };
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+
+    // recipient and forward are exclusive
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: Some("addr0001".to_string()),
+            forward: Some(forward.clone()),
+        })
+        .unwrap(),
+    });
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg,
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::RecipientAndForward {});
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: Some("addr0001".to_string()),
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::new(100000000),
+            })
+            .unwrap(),
+            funds: vec![]
+        }))]
+    );
+
+    // forwarded anchor token is minted to the converter and sent to the market
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(10000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: Some(forward.clone()),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), wormhole_info, receive_msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::new(100000000),
+                })
+                .unwrap(),
+                funds: vec![]
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Send {
+                    contract: market.to_string(),
+                    amount: Uint128::new(100000000),
+                    msg: to_binary(&"deposit_collateral").unwrap(),
+                })
+                .unwrap(),
+                funds: vec![]
+            })),
+        ]
+    );
+    assert_eq!(res.attributes[1], Attribute::new("recipient", market));
+
+    // redeemed wormhole token is sent to the bridge
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: Some(Forward {
+                contract: bridge.to_string(),
+                msg: to_binary(&"bridge_out").unwrap(),
+            }),
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    let res = execute(deps.as_mut(), mock_env(), beth_info.clone(), receive_msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: bridge.to_string(),
+                amount: Uint128::new(10000000000),
+                msg: to_binary(&"bridge_out").unwrap(),
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    // redeemed native coins are attached to the forwarded execution
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        AddNativeSource {
+            denom: "uweth".to_string(),
+            decimals: 8,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[Coin::new(10000000000, "uweth")]),
+        ConvertNativeToAnchor {
+            recipient: None,
+            forward: None,
+        },
+    )
+    .unwrap();
+
+    deps.querier
+        .with_native_balance(MOCK_CONTRACT_ADDR, vec![Coin::new(10000000000, "uweth")]);
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(100000000),
+        msg: to_binary(&ConvertAnchorToNative {
+            denom: "uweth".to_string(),
+            recipient: None,
+            forward: Some(Forward {
+                contract: bridge.to_string(),
+                msg: to_binary(&"bridge_out").unwrap(),
+            }),
+        })
+        .unwrap(),
+    });
+    let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: bridge.to_string(),
+            msg: to_binary(&"bridge_out").unwrap(),
+            funds: vec![Coin::new(9999000000, "uweth")],
+        }))
+    );
+}
+
+#[test]
+fn proper_receive_errors() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, init_msg).unwrap();
+
+    // redemption before the tokens are registered
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let anchor_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let err = execute(deps.as_mut(), mock_env(), anchor_info.clone(), receive_msg).unwrap_err();
+    assert_eq!(err, ContractError::TokensNotRegistered {});
+
+    // malformed hook message
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&"unknown").unwrap(),
+    });
+    let err = execute(deps.as_mut(), mock_env(), anchor_info, receive_msg).unwrap_err();
+    assert_eq!(err, ContractError::UnknownHook {});
+}
+
+#[test]
+fn proper_stats() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, default_init()).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        RegisterTokens {
+            anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap();
+    let stats: StatsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            total_minted: Uint128::zero(),
+            total_burned: Uint128::zero(),
+            to_anchor_conversions: 0,
+            to_wormhole_conversions: 0,
+            sources: vec![],
+        }
+    );
+
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+    for _ in 0..2 {
+        let receive_msg = Receive(Cw20ReceiveMsg {
+            sender: "addr0000".to_string(),
+            amount: Uint128::new(5000000000),
+            msg: to_binary(&ConvertWormholeToAnchor {
+                recipient: None,
+                forward: None,
+            })
+            .unwrap(),
+        });
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            wormhole_info.clone(),
+            receive_msg,
+        )
+        .unwrap();
+    }
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: "addr0000".to_string(),
+        amount: Uint128::new(40000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Stats {}).unwrap();
+    let stats: StatsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        stats,
+        StatsResponse {
+            total_minted: Uint128::new(100000000),
+            total_burned: Uint128::new(40000000),
+            to_anchor_conversions: 2,
+            to_wormhole_conversions: 1,
+            sources: vec![SourceStatsResponse {
+                source: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+                total_deposited: Uint128::new(10000000000),
+                total_returned: Uint128::new(4000000000),
+            }],
+        }
+    );
+}
+
+#[test]
+fn proper_convert_from() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, default_init()).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let sender = "addr0000";
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ConvertFrom {
+            direction: ConvertDirection::ToAnchor { source_token: None },
+            amount: Uint128::zero(),
+            recipient: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ZeroAmount {});
+
+    // the wormhole token is pulled from the allowance before minting
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ConvertFrom {
+            direction: ConvertDirection::ToAnchor { source_token: None },
+            amount: Uint128::new(100000000),
+            recipient: None,
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::TransferFrom {
+                    owner: sender.to_string(),
+                    recipient: MOCK_CONTRACT_ADDR.to_string(),
+                    amount: Uint128::new(100000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Mint {
+                    recipient: sender.to_string(),
+                    amount: Uint128::new(1000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+    assert_eq!(
+        res.attributes[0],
+        Attribute::new("action", "convert-to-anchor")
+    );
+
+    // the anchor token is burned from the allowance
+    hold_backing(&mut deps, MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR);
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ConvertFrom {
+            direction: ConvertDirection::ToWormhole { source_token: None },
+            amount: Uint128::new(1000000),
+            recipient: Some("addr0001".to_string()),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: "addr0001".to_string(),
+                    amount: Uint128::new(100000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+            SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+                contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&Cw20ExecuteMsg::BurnFrom {
+                    owner: sender.to_string(),
+                    amount: Uint128::new(1000000),
+                })
+                .unwrap(),
+                funds: vec![],
+            })),
+        ]
+    );
+
+    // native coins cannot be drawn from an allowance
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        AddNativeSource {
+            denom: "uweth".to_string(),
+            decimals: 6,
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(sender, &[]),
+        ConvertFrom {
+            direction: ConvertDirection::ToAnchor {
+                source_token: Some("uweth".to_string()),
+            },
+            amount: Uint128::new(1000000),
+            recipient: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::NativeAllowance {
+            denom: "uweth".to_string()
+        }
+    );
+}
+
+#[test]
+fn proper_access_lists() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, default_init()).unwrap();
+
+    deps.querier.set_decimals(6, 8);
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        RegisterTokens {
+            anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+
+    let receive_msg = |sender: &str| {
+        Receive(Cw20ReceiveMsg {
+            sender: sender.to_string(),
+            amount: Uint128::new(100000000),
+            msg: to_binary(&ConvertWormholeToAnchor {
+                recipient: None,
+                forward: None,
+            })
+            .unwrap(),
+        })
+    };
+    let wormhole_info = mock_info(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR, &[]);
+
+    let update_denylist = UpdateDenylist {
+        add: vec!["addr0000".to_string(), "addr0001".to_string()],
+        remove: vec![],
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        update_denylist.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        update_denylist,
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg("addr0000"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SenderDenied {
+            sender: "addr0000".to_string()
+        }
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg("addr0002"),
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Denylist {
+            start_after: Some("addr0000".to_string()),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let denylist: DenylistResponse = from_binary(&res).unwrap();
+    assert_eq!(denylist.addresses, vec!["addr0001".to_string()]);
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        UpdateDenylist {
+            add: vec![],
+            remove: vec!["addr0000".to_string()],
+        },
+    )
+    .unwrap();
+
+    // only allowlisted senders can convert in allowlist-only mode
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        SetAllowlistOnly {
+            allowlist_only: true,
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info.clone(),
+        receive_msg("addr0000"),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::SenderNotAllowed {
+            sender: "addr0000".to_string()
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info,
+        UpdateAllowlist {
+            add: vec!["addr0000".to_string()],
+            remove: vec![],
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        wormhole_info,
+        receive_msg("addr0000"),
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Allowlist {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let allowlist: AllowlistResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        allowlist,
+        AllowlistResponse {
+            allowlist_only: true,
+            addresses: vec!["addr0000".to_string()],
+        }
+    );
+}
+
+#[test]
+fn proper_timelock() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, default_init()).unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let update_fees = UpdateFees {
+        fee_collector: Some(MOCK_FEE_COLLECTOR_ADDR.to_string()),
+        to_anchor_fee_bps: Some(10),
+        to_wormhole_fee_bps: None,
+    };
+
+    // the delay is set directly while no timelock is in place
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        SetTimelockDelay {
+            delay: 31 * 24 * 60 * 60,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::TimelockDelayTooLong {
+            max: MAX_TIMELOCK_DELAY
+        }
+    );
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        SetTimelockDelay { delay: 1000 },
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        update_fees.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::MustBeQueued {});
+
+    // only owner operations can be queued, and only by the owner
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ProposeAction {
+            msg: Box::new(update_fees.clone()),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ProposeAction {
+            msg: Box::new(Pause {
+                mint: true,
+                redeem: true,
+            }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::NotTimelocked {});
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ProposeAction {
+            msg: Box::new(update_fees.clone()),
+        },
+    )
+    .unwrap();
+    let eta = mock_env().block.time.seconds() + 1000;
+    assert_eq!(res.attributes[1], Attribute::new("id", "0"));
+    assert_eq!(res.attributes[2], Attribute::new("eta", eta.to_string()));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedActions {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedActionsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        queued,
+        QueuedActionsResponse {
+            delay: 1000,
+            actions: vec![QueuedActionResponse {
+                id: 0,
+                msg: update_fees.clone(),
+                eta,
+            }],
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteAction { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::ActionTimelocked { eta });
+
+    // anyone executes the action once the delay has passed
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1000);
+    let res = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteAction { id: 0 },
+    )
+    .unwrap();
+    assert_eq!(res.attributes[0], Attribute::new("action", "update_fees"));
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.to_anchor_fee_bps, 10);
+
+    let err = execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteAction { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::QueuedActionNotFound { id: 0 });
+
+    // the owner cancels a queued action
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ProposeAction {
+            msg: Box::new(SetTimelockDelay { delay: 0 }),
+        },
+    )
+    .unwrap();
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        CancelAction { id: 1 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::Unauthorized {});
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        CancelAction { id: 1 },
+    )
+    .unwrap();
+
+    let err = query(deps.as_ref(), mock_env(), QueryMsg::QueuedAction { id: 1 }).unwrap_err();
+    assert_eq!(err, ContractError::QueuedActionNotFound { id: 1 });
+
+    // actions queued by a previous owner do not run once ownership changes hands
+    for msg in [
+        update_fees,
+        ProposeNewOwner {
+            new_owner: "new_owner".to_string(),
+            expires_in: None,
+        },
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info.clone(),
+            ProposeAction { msg: Box::new(msg) },
+        )
+        .unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteAction { id: 3 },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new_owner", &[]),
+        AcceptOwnership {},
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteAction { id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(err, ContractError::StaleQueuedAction {});
+
+    // the new owner can still cancel it
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("new_owner", &[]),
+        CancelAction { id: 2 },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedActions {
+            start_after: Some(u64::MAX),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedActionsResponse = from_binary(&res).unwrap();
+    assert_eq!(queued.actions, vec![]);
+}
+
+#[test]
+fn proper_update_config() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let invalid_info = mock_info("invalid", &[]);
+
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+
+    // unauthorized request
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        invalid_info,
+        update_config.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    //successful one
+    let res = execute(deps.as_mut(), mock_env(), info, update_config).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute::new("action", "register_token_contracts")
+    );
+
+    // decimals are stored at registration and not queried again
+    deps.querier.set_decimals(10, 10);
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config_response,
+        ConfigResponse {
+            owner: MOCK_OWNER_ADDR.to_string(),
+            anchor_token_address: Some("beth_token0000".to_string()),
+            wormhole_token_address: Some("wormhole_token0000".to_string()),
+            anchor_decimals: Some(6),
+            wormhole_decimals: Some(8),
+            fee_collector: None,
+            to_anchor_fee_bps: 0,
+            to_wormhole_fee_bps: 0,
+        }
+    );
+}
+
+#[test]
+fn proper_source_tokens() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let invalid_info = mock_info("invalid", &[]);
+
+    let res = instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+    assert_eq!(0, res.messages.len());
+
+    // set anchor, wormhole and source decimals
+    deps.querier.set_decimals(6, 8);
+    deps.querier
+        .with_source_decimals(&[(MOCK_SOURCE_TOKEN_CONTRACT_ADDR, 18)]);
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
+    let add_source = AddSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+
+    // unauthorized request
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        invalid_info.clone(),
+        add_source.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    // successful one
+    let res = execute(deps.as_mut(), mock_env(), info.clone(), add_source.clone()).unwrap();
+    assert_eq!(
+        res.attributes,
+        vec![
+            Attribute::new("action", "add_source_token"),
+            Attribute::new("source_token", MOCK_SOURCE_TOKEN_CONTRACT_ADDR),
+            Attribute::new("decimals", "18"),
+        ]
+    );
+
+    // cannot register twice
+    let error_res = execute(deps.as_mut(), mock_env(), info.clone(), add_source).unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::SourceAlreadyRegistered {
+            token: "source_token0000".to_string()
+        }
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceTokens {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let source_tokens: SourceTokensResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        source_tokens.source_tokens,
+        vec![
+            SourceTokenResponse {
+                token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+                decimals: 18,
+                enabled: true,
+                backing: Uint128::zero(),
+            },
+            SourceTokenResponse {
+                token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+                decimals: 8,
+                enabled: true,
+                backing: Uint128::zero(),
+            },
+        ]
+    );
+
+    // disable the new source
+    let update_source = UpdateSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+        enabled: false,
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        invalid_info.clone(),
+        update_source.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+    execute(deps.as_mut(), mock_env(), info.clone(), update_source).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let source_token: SourceTokenResponse = from_binary(&res).unwrap();
+    assert!(!source_token.enabled);
+
+    // the registered wormhole token cannot be removed
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        info.clone(),
+        RemoveSourceToken {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::WormholeTokenRemoval {});
+
+    let remove_source = RemoveSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    let error_res = execute(
+        deps.as_mut(),
+        mock_env(),
+        invalid_info,
+        remove_source.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(error_res, ContractError::Unauthorized {});
+
+    let res = execute(deps.as_mut(), mock_env(), info, remove_source).unwrap();
+    assert_eq!(
+        res.attributes[0],
+        Attribute::new("action", "remove_source_token")
+    );
+
+    let error_res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::SourceNotRegistered {
+            token: "source_token0000".to_string()
+        }
+    );
+}
+
+#[test]
+fn proper_convert_with_multiple_sources() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+
+    let sender = "addr0000";
+    let info = mock_info(MOCK_OWNER_ADDR, &[]);
+
+    instantiate(deps.as_mut(), mock_env(), info.clone(), init_msg).unwrap();
+
+    // set anchor, wormhole and source decimals
+    deps.querier.set_decimals(6, 8);
+    deps.querier
+        .with_source_decimals(&[(MOCK_SOURCE_TOKEN_CONTRACT_ADDR, 18)]);
+
+    let update_config = RegisterTokens {
+        anchor_token_address: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+        wormhole_token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), update_config).unwrap();
+
+    let add_source = AddSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), info.clone(), add_source).unwrap();
+
+    // deposit the second source
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000000000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let source_info = mock_info(MOCK_SOURCE_TOKEN_CONTRACT_ADDR, &[]);
+    let res = execute(deps.as_mut(), mock_env(), source_info.clone(), receive_msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_ANCHOR_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Mint {
+                recipient: sender.to_string(),
+                // 10^18 / 10^12 = 10^6
+                amount: Uint128::new(1000000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+    assert_eq!(
+        res.attributes[2],
+        Attribute::new("source_token", MOCK_SOURCE_TOKEN_CONTRACT_ADDR)
+    );
+
+    // the default wormhole token has nothing deposited
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: None,
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let beth_info = mock_info(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR, &[]);
+    let error_res = execute(deps.as_mut(), mock_env(), beth_info.clone(), receive_msg).unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::InsufficientBacking {
+            backing: Uint128::zero()
+        }
+    );
+
+    // redeem half into the second source
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(500000),
+        msg: to_binary(&ConvertAnchorToWormhole {
+            source_token: Some(MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string()),
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    hold_backing(&mut deps, MOCK_SOURCE_TOKEN_CONTRACT_ADDR);
+    let res = execute(deps.as_mut(), mock_env(), beth_info, receive_msg).unwrap();
+    assert_eq!(
+        res.messages[0],
+        SubMsg::new(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: sender.to_string(),
+                amount: Uint128::new(500000000000000000)
+            })
+            .unwrap(),
+            funds: vec![]
+        }))
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let source_token: SourceTokenResponse = from_binary(&res).unwrap();
+    assert_eq!(source_token.backing, Uint128::new(500000000000000000));
+
+    // a source with backing cannot be removed
+    let remove_source = RemoveSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    let error_res = execute(deps.as_mut(), mock_env(), info.clone(), remove_source).unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::SourceStillBacking {
+            backing: Uint128::new(500000000000000000)
+        }
+    );
+
+    // a disabled source cannot be deposited
+    let update_source = UpdateSourceToken {
+        token_address: MOCK_SOURCE_TOKEN_CONTRACT_ADDR.to_string(),
+        enabled: false,
+    };
+    execute(deps.as_mut(), mock_env(), info, update_source).unwrap();
+
+    let receive_msg = Receive(Cw20ReceiveMsg {
+        sender: sender.to_string(),
+        amount: Uint128::new(1000000000000000000),
+        msg: to_binary(&ConvertWormholeToAnchor {
+            recipient: None,
+            forward: None,
+        })
+        .unwrap(),
+    });
+    let error_res = execute(deps.as_mut(), mock_env(), source_info, receive_msg).unwrap_err();
+    assert_eq!(
+        error_res,
+        ContractError::SourceDisabled {
+            token: "source_token0000".to_string()
+        }
+    );
+}
+
+// config as stored by the instances deployed before the source token registry
+fn store_legacy_config(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>) {
+    let canonical = |address: &str| deps.api.addr_canonicalize(address).unwrap().0.to_base64();
+    let config = format!(
+        r#"{{"owner":"{}","anchor_token_address":"{}","wormhole_token_address":"{}"}}"#,
+        canonical(MOCK_OWNER_ADDR),
+        canonical(MOCK_ANCHOR_TOKEN_CONTRACT_ADDR),
+        canonical(MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR),
+    );
+    deps.storage
+        .set(&to_length_prefixed(KEY_CONFIG), config.as_bytes());
+}
+
+#[test]
+fn proper_migrate() {
+    let mut deps = mock_dependencies(&[]);
+
+    // instance deployed before the source token registry
+    store_legacy_config(&mut deps);
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(1000000))],
+    )]);
+
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { params: vec![] }).unwrap();
+    assert_eq!(res.attributes[1], Attribute::new("from_version", "0.0.0"));
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let source_token: SourceTokenResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        source_token,
+        SourceTokenResponse {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+            decimals: 8,
+            enabled: true,
+            backing: Uint128::new(1000000),
+        }
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config_response: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config_response.anchor_decimals, Some(6));
+    assert_eq!(config_response.wormhole_decimals, Some(8));
+}
+
+#[test]
+fn proper_migrate_versioning() {
+    let mut deps = mock_dependencies(&[]);
+    let info = mock_info("addr0000", &[]);
+    instantiate(deps.as_mut(), mock_env(), info, default_init()).unwrap();
+
+    let version = get_contract_version(&deps.storage).unwrap();
+    assert_eq!(version.contract, CONTRACT_NAME);
+    assert_eq!(version.version, CONTRACT_VERSION);
+
+    // migrating to the same version is a no-op
+    let res = migrate(deps.as_mut(), mock_env(), MigrateMsg { params: vec![] }).unwrap();
+    assert_eq!(
+        res.attributes[1],
+        Attribute::new("from_version", CONTRACT_VERSION)
+    );
+
+    // refuse downgrades
+    set_contract_version(&mut deps.storage, CONTRACT_NAME, "99.0.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { params: vec![] }).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::CannotDowngrade {
+            from: "99.0.0".to_string(),
+            to: CONTRACT_VERSION.to_string(),
+        }
+    );
+
+    // refuse other contracts
+    set_contract_version(&mut deps.storage, "crates.io:cw20-base", "0.1.0").unwrap();
+    let err = migrate(deps.as_mut(), mock_env(), MigrateMsg { params: vec![] }).unwrap_err();
+    assert_eq!(
+        err,
+        ContractError::InvalidContractName {
+            expected: CONTRACT_NAME.to_string(),
+            found: "crates.io:cw20-base".to_string(),
+        }
+    );
+}
+
+#[test]
+fn proper_migrate_with_params() {
+    let mut deps = mock_dependencies(&[]);
+
+    store_legacy_config(&mut deps);
+    deps.querier.with_token_balances(&[(
+        MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR,
+        &[(MOCK_CONTRACT_ADDR, &Uint128::new(1000000))],
+    )]);
+
+    // tokens sent to the converter without a conversion do not back anything
+    migrate(
+        deps.as_mut(),
+        mock_env(),
+        MigrateMsg {
+            params: vec![MigrationParams::V010 {
+                wormhole_backing: Some(Uint128::new(900000)),
+            }],
+        },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::SourceToken {
+            token_address: MOCK_WORMHOLE_TOKEN_CONTRACT_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    let source_token: SourceTokenResponse = from_binary(&res).unwrap();
+    assert_eq!(source_token.backing, Uint128::new(900000));
+    assert_eq!(
+        get_contract_version(&deps.storage).unwrap().version,
+        CONTRACT_VERSION
+    );
 }
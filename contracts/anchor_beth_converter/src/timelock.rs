@@ -0,0 +1,168 @@
+use crate::contract::dispatch;
+use crate::error::ContractError;
+use crate::state::{
+    read_config, read_queued_action, read_queued_actions, read_timelock, remove_queued_action,
+    store_queued_action, store_timelock, QueuedAction,
+};
+
+use beth::converter::{ExecuteMsg, QueuedActionResponse, QueuedActionsResponse};
+use cosmwasm_std::{Deps, DepsMut, Env, MessageInfo, Response, StdResult};
+
+// the delay cannot lock the owner out for longer than 30 days
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// Owner operations that have to be queued once a timelock delay is set.
+/// Pausing, unpausing and the sender lists stay immediate, as they are emergency
+/// and compliance controls; token migrations have their own timelock.
+pub(crate) fn is_timelocked(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::RegisterTokens { .. }
+            | ExecuteMsg::ProposeNewOwner { .. }
+            | ExecuteMsg::AddSourceToken { .. }
+            | ExecuteMsg::UpdateSourceToken { .. }
+            | ExecuteMsg::RemoveSourceToken { .. }
+            | ExecuteMsg::AddNativeSource { .. }
+            | ExecuteMsg::UpdateNativeSource { .. }
+            | ExecuteMsg::RemoveNativeSource { .. }
+            | ExecuteMsg::UpdateFees { .. }
+            | ExecuteMsg::SetGuardian { .. }
+            | ExecuteMsg::SetAllowlistOnly { .. }
+            | ExecuteMsg::UpdateRateLimit { .. }
+            | ExecuteMsg::SweepSurplus { .. }
+            | ExecuteMsg::RecoverToken { .. }
+            | ExecuteMsg::SetTimelockDelay { .. }
+    )
+}
+
+pub fn propose_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if !is_timelocked(&msg) {
+        return Err(ContractError::NotTimelocked {});
+    }
+
+    let mut timelock = read_timelock(deps.storage)?;
+    let id = timelock.next_action_id;
+    let eta = env
+        .block
+        .time
+        .seconds()
+        .checked_add(timelock.delay)
+        .ok_or(ContractError::InvalidActionEta {})?;
+    store_queued_action(
+        deps.storage,
+        id,
+        &QueuedAction {
+            msg,
+            eta,
+            proposer: config.owner,
+        },
+    )?;
+    timelock.next_action_id += 1;
+    store_timelock(deps.storage, &timelock)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose_action"),
+        ("id", &id.to_string()),
+        ("eta", &eta.to_string()),
+    ]))
+}
+
+pub fn cancel_action(deps: DepsMut, info: MessageInfo, id: u64) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if read_queued_action(deps.storage, id)?.is_none() {
+        return Err(ContractError::QueuedActionNotFound { id });
+    }
+    remove_queued_action(deps.storage, id);
+
+    Ok(Response::new().add_attributes(vec![("action", "cancel_action"), ("id", &id.to_string())]))
+}
+
+/// Run a queued operation on behalf of the owner that proposed it
+pub fn execute_action(deps: DepsMut, env: Env, id: u64) -> Result<Response, ContractError> {
+    let action =
+        read_queued_action(deps.storage, id)?.ok_or(ContractError::QueuedActionNotFound { id })?;
+    if env.block.time.seconds() < action.eta {
+        return Err(ContractError::ActionTimelocked { eta: action.eta });
+    }
+
+    // actions queued by a previous owner must not run with the new owner's authority
+    let config = read_config(deps.storage)?;
+    if action.proposer != config.owner {
+        return Err(ContractError::StaleQueuedAction {});
+    }
+    remove_queued_action(deps.storage, id);
+
+    let owner = deps.api.addr_humanize(&config.owner)?;
+    let owner_info = MessageInfo {
+        sender: owner,
+        funds: vec![],
+    };
+    let res = dispatch(deps, env, owner_info, action.msg)?;
+
+    Ok(res.add_attribute("queued_action_id", id.to_string()))
+}
+
+pub fn set_timelock_delay(
+    deps: DepsMut,
+    info: MessageInfo,
+    delay: u64,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    if delay > MAX_TIMELOCK_DELAY {
+        return Err(ContractError::TimelockDelayTooLong {
+            max: MAX_TIMELOCK_DELAY,
+        });
+    }
+
+    let mut timelock = read_timelock(deps.storage)?;
+    timelock.delay = delay;
+    store_timelock(deps.storage, &timelock)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "set_timelock_delay"),
+        ("delay", &delay.to_string()),
+    ]))
+}
+
+pub fn query_queued_action(deps: Deps, id: u64) -> Result<QueuedActionResponse, ContractError> {
+    let action =
+        read_queued_action(deps.storage, id)?.ok_or(ContractError::QueuedActionNotFound { id })?;
+
+    Ok(QueuedActionResponse {
+        id,
+        msg: action.msg,
+        eta: action.eta,
+    })
+}
+
+pub fn query_queued_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<QueuedActionsResponse> {
+    Ok(QueuedActionsResponse {
+        delay: read_timelock(deps.storage)?.delay,
+        actions: read_queued_actions(deps.storage, start_after, limit)?,
+    })
+}
@@ -0,0 +1,243 @@
+use crate::contract::load_source_token;
+use crate::error::ContractError;
+use crate::math::convert_decimals;
+use crate::querier::{query_decimals, query_token_balance};
+use crate::state::{
+    read_config, read_source_token, read_token_migration, remove_source_token,
+    remove_token_migration, store_config, store_source_token, store_token_migration, SourceToken,
+    TokenMigration,
+};
+
+use beth::converter::{ExecuteMsg, TokenMigrationResponse, TokenMigrationStatus};
+use cosmwasm_std::{
+    to_binary, Binary, CosmosMsg, Deps, DepsMut, Env, MessageInfo, Response, Uint128, WasmMsg,
+};
+use cw20::Cw20ExecuteMsg;
+
+// a proposed token migration can only be executed after 3 days
+pub const TOKEN_MIGRATION_DELAY: u64 = 3 * 24 * 60 * 60;
+
+pub fn propose_token_migration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_token_address: String,
+    migration_contract: String,
+    migration_msg: Binary,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let legacy_token = config
+        .wormhole_token_address
+        .ok_or(ContractError::TokensNotRegistered {})?;
+
+    if let Some(token_migration) = read_token_migration(deps.storage)? {
+        if !token_migration.completed {
+            return Err(ContractError::TokenMigrationAlreadyPending {});
+        }
+    }
+
+    let new_token_addr = deps.api.addr_validate(&new_token_address)?;
+    let new_token = deps.api.addr_canonicalize(new_token_addr.as_str())?;
+    if read_source_token(deps.storage, &new_token)?.is_some() {
+        return Err(ContractError::SourceAlreadyRegistered {
+            token: new_token_addr.to_string(),
+        });
+    }
+    let new_decimals = query_decimals(deps.as_ref(), new_token_addr)?;
+
+    let eta = env.block.time.seconds() + TOKEN_MIGRATION_DELAY;
+    store_token_migration(
+        deps.storage,
+        &TokenMigration {
+            legacy_token,
+            new_token,
+            new_decimals,
+            migration_contract: deps.api.addr_canonicalize(&migration_contract)?,
+            migration_msg,
+            eta,
+            migrated_amount: Uint128::zero(),
+            completed: false,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "propose_token_migration"),
+        ("new_token", &new_token_address),
+        ("migration_contract", &migration_contract),
+        ("eta", &eta.to_string()),
+    ]))
+}
+
+pub fn cancel_token_migration(deps: DepsMut, info: MessageInfo) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    load_pending_token_migration(deps.as_ref())?;
+    remove_token_migration(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![("action", "cancel_token_migration")]))
+}
+
+pub fn execute_token_migration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    let config = read_config(deps.storage)?;
+
+    if config.owner != deps.api.addr_canonicalize(info.sender.as_str())? {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut token_migration = load_pending_token_migration(deps.as_ref())?;
+    if env.block.time.seconds() < token_migration.eta {
+        return Err(ContractError::TokenMigrationTimelocked {
+            eta: token_migration.eta,
+        });
+    }
+
+    // only the deposited backing is migrated, any surplus stays in the legacy token
+    let legacy = load_source_token(deps.as_ref(), &token_migration.legacy_token)?;
+    token_migration.migrated_amount = legacy.backing;
+    store_token_migration(deps.storage, &token_migration)?;
+
+    let mut messages = vec![];
+    if !legacy.backing.is_zero() {
+        messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+            contract_addr: deps
+                .api
+                .addr_humanize(&token_migration.legacy_token)?
+                .to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Send {
+                contract: deps
+                    .api
+                    .addr_humanize(&token_migration.migration_contract)?
+                    .to_string(),
+                amount: legacy.backing,
+                msg: token_migration.migration_msg,
+            })?,
+            funds: vec![],
+        }));
+    }
+    // the new token is checked once the migration contract has returned it
+    messages.push(CosmosMsg::Wasm(WasmMsg::Execute {
+        contract_addr: env.contract.address.to_string(),
+        msg: to_binary(&ExecuteMsg::FinalizeTokenMigration {})?,
+        funds: vec![],
+    }));
+
+    Ok(Response::new().add_messages(messages).add_attributes(vec![
+        ("action", "execute_token_migration"),
+        ("migrated_amount", &legacy.backing.to_string()),
+    ]))
+}
+
+pub fn finalize_token_migration(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> Result<Response, ContractError> {
+    // only the converter itself can finalize a migration
+    if info.sender != env.contract.address {
+        return Err(ContractError::Unauthorized {});
+    }
+
+    let mut token_migration = load_pending_token_migration(deps.as_ref())?;
+    let legacy = load_source_token(deps.as_ref(), &token_migration.legacy_token)?;
+    if read_source_token(deps.storage, &token_migration.new_token)?.is_some() {
+        return Err(ContractError::SourceAlreadyRegistered {
+            token: deps
+                .api
+                .addr_humanize(&token_migration.new_token)?
+                .to_string(),
+        });
+    }
+
+    // the new token must back everything the legacy token backed
+    let backing = convert_decimals(
+        token_migration.migrated_amount,
+        legacy.decimals,
+        token_migration.new_decimals,
+    );
+    let balance = query_token_balance(
+        deps.as_ref(),
+        deps.api.addr_humanize(&token_migration.new_token)?,
+        env.contract.address,
+    )?;
+    if balance < backing {
+        return Err(ContractError::TokenMigrationShortfall {
+            balance,
+            expected: backing,
+        });
+    }
+
+    remove_source_token(deps.storage, &token_migration.legacy_token);
+    store_source_token(
+        deps.storage,
+        &token_migration.new_token,
+        &SourceToken {
+            decimals: token_migration.new_decimals,
+            enabled: legacy.enabled,
+            backing,
+        },
+    )?;
+
+    let mut config = read_config(deps.storage)?;
+    config.wormhole_token_address = Some(token_migration.new_token.clone());
+    store_config(deps.storage).save(&config)?;
+
+    token_migration.completed = true;
+    store_token_migration(deps.storage, &token_migration)?;
+
+    Ok(Response::new().add_attributes(vec![
+        ("action", "finalize_token_migration"),
+        (
+            "new_token",
+            deps.api.addr_humanize(&token_migration.new_token)?.as_str(),
+        ),
+        ("backing", &backing.to_string()),
+    ]))
+}
+
+fn load_pending_token_migration(deps: Deps) -> Result<TokenMigration, ContractError> {
+    match read_token_migration(deps.storage)? {
+        Some(token_migration) if !token_migration.completed => Ok(token_migration),
+        _ => Err(ContractError::NoPendingTokenMigration {}),
+    }
+}
+
+pub fn query_token_migration(deps: Deps) -> Result<TokenMigrationResponse, ContractError> {
+    let token_migration =
+        read_token_migration(deps.storage)?.ok_or(ContractError::NoTokenMigration {})?;
+
+    Ok(TokenMigrationResponse {
+        legacy_token_address: deps
+            .api
+            .addr_humanize(&token_migration.legacy_token)?
+            .to_string(),
+        new_token_address: deps
+            .api
+            .addr_humanize(&token_migration.new_token)?
+            .to_string(),
+        migration_contract: deps
+            .api
+            .addr_humanize(&token_migration.migration_contract)?
+            .to_string(),
+        migration_msg: token_migration.migration_msg,
+        eta: token_migration.eta,
+        migrated_amount: token_migration.migrated_amount,
+        status: if token_migration.completed {
+            TokenMigrationStatus::Completed
+        } else {
+            TokenMigrationStatus::Pending
+        },
+    })
+}
@@ -2,8 +2,11 @@ use std::env::current_dir;
 use std::fs::create_dir_all;
 
 use beth::reward::{
-    AccruedRewardsResponse, ConfigResponse, ExecuteMsg, HolderResponse, HoldersResponse,
-    InstantiateMsg, QueryMsg, StateResponse,
+    AccruedRewardsResponse, AccruedTokenRewardsResponse, ConfigResponse, Cw20HookMsg,
+    DistributionResponse, ExecuteMsg, HolderIndexResponse, HolderResponse,
+    HolderTokenIndexResponse, HoldersResponse, InstantiateMsg, MigrateMsg, PendingOwnerResponse,
+    QueryMsg, QueuedActionResponse, QueuedActionsResponse, RewardIndexResponse,
+    RewardStreamResponse, RewardTokenResponse, RewardTokensResponse, StateResponse,
 };
 use cosmwasm_schema::{export_schema, remove_schemas, schema_for};
 
@@ -16,9 +19,22 @@ fn main() {
     export_schema(&schema_for!(InstantiateMsg), &out_dir);
     export_schema(&schema_for!(ExecuteMsg), &out_dir);
     export_schema(&schema_for!(QueryMsg), &out_dir);
+    export_schema(&schema_for!(MigrateMsg), &out_dir);
+    export_schema(&schema_for!(Cw20HookMsg), &out_dir);
     export_schema(&schema_for!(ConfigResponse), &out_dir);
+    export_schema(&schema_for!(PendingOwnerResponse), &out_dir);
     export_schema(&schema_for!(StateResponse), &out_dir);
+    export_schema(&schema_for!(RewardIndexResponse), &out_dir);
     export_schema(&schema_for!(AccruedRewardsResponse), &out_dir);
+    export_schema(&schema_for!(AccruedTokenRewardsResponse), &out_dir);
+    export_schema(&schema_for!(RewardTokenResponse), &out_dir);
+    export_schema(&schema_for!(RewardTokensResponse), &out_dir);
+    export_schema(&schema_for!(DistributionResponse), &out_dir);
+    export_schema(&schema_for!(RewardStreamResponse), &out_dir);
     export_schema(&schema_for!(HolderResponse), &out_dir);
+    export_schema(&schema_for!(HolderIndexResponse), &out_dir);
+    export_schema(&schema_for!(HolderTokenIndexResponse), &out_dir);
     export_schema(&schema_for!(HoldersResponse), &out_dir);
+    export_schema(&schema_for!(QueuedActionResponse), &out_dir);
+    export_schema(&schema_for!(QueuedActionsResponse), &out_dir);
 }
@@ -1,20 +1,38 @@
 #[cfg(not(feature = "library"))]
 use cosmwasm_std::entry_point;
 
-use crate::owner::{execute_post_initialize, execute_update_config};
+use crate::migration::{migrate_contract, CONTRACT_NAME, CONTRACT_VERSION};
+use crate::owner::{
+    execute_accept_ownership, execute_add_reward_denom, execute_add_reward_token,
+    execute_cancel_ownership_transfer, execute_post_initialize, execute_propose_new_owner,
+    execute_update_distribution_period, query_pending_owner,
+};
+use crate::reward_token::{execute_receive, query_accrued_token_rewards, query_reward_tokens};
+use crate::rewards::{execute_deposit_rewards, execute_recover_unaccounted};
 use crate::state::{
-    read_config, read_state, store_config, store_contract_addr, store_state, Config, State,
+    read_config, read_state, read_timelock, store_config, store_contract_addr, store_state, Config,
+    RewardIndex, State, DEFAULT_SWAP_DUST_THRESHOLD,
+};
+use crate::stream::query_distribution;
+use crate::swap::{execute_swap, execute_update_global_index, execute_update_swap_config};
+use crate::timelock::{
+    execute_cancel_action, execute_propose_action, execute_queued_action,
+    execute_set_timelock_delay, is_timelocked, query_queued_action, query_queued_actions,
 };
 use crate::user::{
     execute_claim_rewards, execute_decrease_balance, execute_increase_balance,
-    query_accrued_rewards, query_holder, query_holders,
+    execute_set_withdraw_address, query_accrued_rewards, query_holder, query_holders,
+};
+use beth::reward::{
+    ConfigResponse, ExecuteMsg, InstantiateMsg, MigrateMsg, QueryMsg, RewardIndexResponse,
+    StateResponse,
 };
-use beth::reward::{ConfigResponse, ExecuteMsg, InstantiateMsg, QueryMsg, StateResponse};
 use cosmwasm_std::{
-    to_binary, Addr, Api, Binary, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdResult,
+    to_binary, Addr, Api, Binary, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
     Uint128,
 };
 
+use cw2::set_contract_version;
 use terra_cosmwasm::TerraMsgWrapper;
 
 #[cfg_attr(not(feature = "library"), entry_point)]
@@ -24,19 +42,34 @@ pub fn instantiate(
     _info: MessageInfo,
     msg: InstantiateMsg,
 ) -> StdResult<Response> {
+    let mut reward_denoms: Vec<String> = vec![];
+    for denom in msg.reward_denoms {
+        if reward_denoms.contains(&denom) {
+            return Err(StdError::generic_err(format!(
+                "reward denom {} is already distributed",
+                denom
+            )));
+        }
+        reward_denoms.push(denom);
+    }
+
     let conf = Config {
         owner: deps.api.addr_canonicalize(&msg.owner)?,
-        reward_denom: msg.reward_denom,
+        reward_denoms: reward_denoms.clone(),
+        reward_tokens: vec![],
         token_contract: None,
+        distribution_period: 0,
+        swap_skip_denoms: vec![],
+        swap_dust_threshold: Uint128::new(DEFAULT_SWAP_DUST_THRESHOLD),
     };
 
     store_config(deps.storage, &conf)?;
     store_state(
         deps.storage,
         &State {
-            global_index: Decimal::zero(),
             total_balance: Uint128::zero(),
-            prev_reward_balance: Uint128::zero(),
+            reward_indices: reward_denoms.into_iter().map(RewardIndex::new).collect(),
+            token_indices: vec![],
         },
     )?;
 
@@ -46,6 +79,8 @@ pub fn instantiate(
         &deps.api.addr_canonicalize(env.contract.address.as_str())?,
     )?;
 
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
     Ok(Response::default())
 }
 
@@ -55,20 +90,71 @@ pub fn execute(
     env: Env,
     info: MessageInfo,
     msg: ExecuteMsg,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    // once a delay is set, owner operations only run through the timelock
+    if is_timelocked(&msg) && read_timelock(deps.storage)?.delay > 0 {
+        return Err(StdError::generic_err(
+            "owner operations must be queued through the timelock",
+        ));
+    }
+
+    dispatch(deps, env, info, msg)
+}
+
+pub(crate) fn dispatch(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
 ) -> StdResult<Response<TerraMsgWrapper>> {
     match msg {
         ExecuteMsg::ClaimRewards { recipient } => {
             let api = deps.api;
             execute_claim_rewards(deps, env, info, optional_addr_validate(api, recipient)?)
         }
+        ExecuteMsg::SetWithdrawAddress { address } => {
+            let address = deps.api.addr_validate(&address)?;
+            execute_set_withdraw_address(deps, info, address)
+        }
         ExecuteMsg::PostInitialize { token_contract } => {
             let token_addr = deps.api.addr_validate(&token_contract)?;
             execute_post_initialize(deps, info, token_addr)
         }
-        ExecuteMsg::UpdateConfig { owner } => {
-            let owner_addr = deps.api.addr_validate(&owner)?;
-            execute_update_config(deps, info, owner_addr)
+        ExecuteMsg::AddRewardDenom { denom } => execute_add_reward_denom(deps, info, denom),
+        ExecuteMsg::AddRewardToken { token_address } => {
+            let token_addr = deps.api.addr_validate(&token_address)?;
+            execute_add_reward_token(deps, info, token_addr)
+        }
+        ExecuteMsg::UpdateDistributionPeriod {
+            distribution_period,
+        } => execute_update_distribution_period(deps, env, info, distribution_period),
+        ExecuteMsg::UpdateSwapConfig {
+            skip_denoms,
+            dust_threshold,
+        } => execute_update_swap_config(deps, info, skip_denoms, dust_threshold),
+        ExecuteMsg::SwapToRewardDenom {} => execute_swap(deps, env),
+        ExecuteMsg::UpdateGlobalIndex {
+            prev_reward_balance,
+        } => execute_update_global_index(deps, env, info, prev_reward_balance),
+        ExecuteMsg::RecoverUnaccounted { recipient } => {
+            let recipient_addr = deps.api.addr_validate(&recipient)?;
+            execute_recover_unaccounted(deps, env, info, recipient_addr)
+        }
+        ExecuteMsg::Receive(msg) => execute_receive(deps, env, info, msg),
+        ExecuteMsg::DepositRewards {} => execute_deposit_rewards(deps, env, info),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner,
+            expires_in,
+        } => {
+            let owner_addr = deps.api.addr_validate(&new_owner)?;
+            execute_propose_new_owner(deps, env, info, owner_addr, expires_in)
         }
+        ExecuteMsg::CancelOwnershipTransfer {} => execute_cancel_ownership_transfer(deps, info),
+        ExecuteMsg::ProposeAction { msg } => execute_propose_action(deps, env, info, *msg),
+        ExecuteMsg::CancelAction { id } => execute_cancel_action(deps, info, id),
+        ExecuteMsg::SetTimelockDelay { delay } => execute_set_timelock_delay(deps, info, delay),
+        ExecuteMsg::ExecuteAction { id } => execute_queued_action(deps, env, id),
+        ExecuteMsg::AcceptOwnership {} => execute_accept_ownership(deps, env, info),
         ExecuteMsg::IncreaseBalance { address, amount } => {
             let addr = deps.api.addr_validate(&address)?;
             execute_increase_balance(deps, env, info, addr, amount)
@@ -91,14 +177,20 @@ fn optional_addr_validate(api: &dyn Api, addr: Option<String>) -> StdResult<Opti
 }
 
 #[cfg_attr(not(feature = "library"), entry_point)]
-pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
+pub fn query(deps: Deps, env: Env, msg: QueryMsg) -> StdResult<Binary> {
     match msg {
         QueryMsg::Config {} => to_binary(&query_config(deps)?),
         QueryMsg::State {} => to_binary(&query_state(deps)?),
         QueryMsg::AccruedRewards { address } => {
             let addr = deps.api.addr_validate(&address)?;
-            to_binary(&query_accrued_rewards(deps, addr)?)
+            to_binary(&query_accrued_rewards(deps, env, addr)?)
         }
+        QueryMsg::AccruedTokenRewards { address } => {
+            let addr = deps.api.addr_validate(&address)?;
+            to_binary(&query_accrued_token_rewards(deps, env, addr)?)
+        }
+        QueryMsg::RewardTokens {} => to_binary(&query_reward_tokens(deps)?),
+        QueryMsg::Distribution {} => to_binary(&query_distribution(deps, env)?),
         QueryMsg::Holder { address } => {
             let addr = deps.api.addr_validate(&address)?;
             to_binary(&query_holder(deps, addr)?)
@@ -111,6 +203,11 @@ pub fn query(deps: Deps, _env: Env, msg: QueryMsg) -> StdResult<Binary> {
                 limit,
             )?)
         }
+        QueryMsg::PendingOwner {} => to_binary(&query_pending_owner(deps)?),
+        QueryMsg::QueuedAction { id } => to_binary(&query_queued_action(deps, id)?),
+        QueryMsg::QueuedActions { start_after, limit } => {
+            to_binary(&query_queued_actions(deps, start_after, limit)?)
+        }
     }
 }
 
@@ -118,8 +215,16 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
     let config: Config = read_config(deps.storage)?;
     let mut res = ConfigResponse {
         owner: deps.api.addr_humanize(&config.owner)?.to_string(),
-        reward_denom: config.reward_denom,
+        reward_denoms: config.reward_denoms,
+        reward_tokens: config
+            .reward_tokens
+            .iter()
+            .map(|token| Ok(deps.api.addr_humanize(token)?.to_string()))
+            .collect::<StdResult<Vec<_>>>()?,
         token_contract: None,
+        distribution_period: config.distribution_period,
+        swap_skip_denoms: config.swap_skip_denoms,
+        swap_dust_threshold: config.swap_dust_threshold,
     };
 
     if let Some(token_contract) = config.token_contract {
@@ -132,8 +237,20 @@ fn query_config(deps: Deps) -> StdResult<ConfigResponse> {
 fn query_state(deps: Deps) -> StdResult<StateResponse> {
     let state: State = read_state(deps.storage)?;
     Ok(StateResponse {
-        global_index: state.global_index,
         total_balance: state.total_balance,
-        prev_reward_balance: state.prev_reward_balance,
+        reward_indices: state
+            .reward_indices
+            .into_iter()
+            .map(|reward_index| RewardIndexResponse {
+                denom: reward_index.denom,
+                global_index: reward_index.global_index,
+                prev_reward_balance: reward_index.prev_reward_balance,
+            })
+            .collect(),
     })
 }
+
+#[cfg_attr(not(feature = "library"), entry_point)]
+pub fn migrate(deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    migrate_contract(deps, env, msg)
+}
@@ -2,7 +2,13 @@ pub mod contract;
 pub mod state;
 
 mod math;
+mod migration;
 mod owner;
+mod reward_token;
+mod rewards;
+mod stream;
+mod swap;
+mod timelock;
 mod user;
 
 #[cfg(test)]
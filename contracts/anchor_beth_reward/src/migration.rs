@@ -0,0 +1,152 @@
+use crate::math::decimal_summation_in_256;
+use crate::state::{
+    read_legacy_config, read_legacy_state, read_state, store_config, store_legacy_reward_denom,
+    store_state, Config, RewardIndex, State, Stream, DEFAULT_SWAP_DUST_THRESHOLD,
+};
+
+use beth::reward::MigrateMsg;
+use cosmwasm_std::{attr, Decimal, DepsMut, Env, Response, StdError, StdResult, Storage, Uint128};
+use cw2::{get_contract_version, set_contract_version, ContractVersion};
+
+pub const CONTRACT_NAME: &str = "crates.io:anchor_beth_reward";
+pub const CONTRACT_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// instances deployed before the version was stored are treated as this version
+const LEGACY_VERSION: &str = "1.2.0";
+
+type Migration = fn(DepsMut, &Env, &MigrateMsg) -> StdResult<()>;
+
+/// state migrations in ascending order, each tagged with the version introducing it
+const MIGRATIONS: &[(&str, Migration)] = &[
+    ("1.3.0", migrate_multi_denom),
+    ("1.3.0", migrate_balance_diff_rewards),
+];
+
+pub fn migrate_contract(mut deps: DepsMut, env: Env, msg: MigrateMsg) -> StdResult<Response> {
+    let stored = read_contract_version(deps.storage)?;
+    if stored.contract != CONTRACT_NAME {
+        return Err(StdError::generic_err(format!(
+            "cannot migrate from contract {}",
+            stored.contract
+        )));
+    }
+
+    let from = parse_version(&stored.version)?;
+    let to = parse_version(CONTRACT_VERSION)?;
+    if from > to {
+        return Err(StdError::generic_err(format!(
+            "cannot downgrade from version {} to {}",
+            stored.version, CONTRACT_VERSION
+        )));
+    }
+
+    // run every migration between the stored version and this one
+    for (version, migration) in MIGRATIONS {
+        let version = parse_version(version)?;
+        if from < version && version <= to {
+            migration(deps.branch(), &env, &msg)?;
+        }
+    }
+
+    set_contract_version(deps.storage, CONTRACT_NAME, CONTRACT_VERSION)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "migrate"),
+        attr("from_version", stored.version),
+        attr("to_version", CONTRACT_VERSION),
+    ]))
+}
+
+fn read_contract_version(storage: &dyn Storage) -> StdResult<ContractVersion> {
+    match get_contract_version(storage) {
+        Ok(version) => Ok(version),
+        Err(StdError::NotFound { .. }) => Ok(ContractVersion {
+            contract: CONTRACT_NAME.to_string(),
+            version: LEGACY_VERSION.to_string(),
+        }),
+        Err(err) => Err(err),
+    }
+}
+
+/// parse a `major.minor.patch` version so it can be ordered
+fn parse_version(version: &str) -> StdResult<(u64, u64, u64)> {
+    let mut parts = version.split('.').map(|part| part.parse::<u64>());
+    match (parts.next(), parts.next(), parts.next(), parts.next()) {
+        (Some(Ok(major)), Some(Ok(minor)), Some(Ok(patch)), None) => Ok((major, minor, patch)),
+        _ => Err(StdError::generic_err(format!(
+            "invalid contract version {}",
+            version
+        ))),
+    }
+}
+
+/// Move the single reward denom index of the config and the state into the first
+/// entry of the per-denom indices. The holders are converted lazily when read.
+fn migrate_multi_denom(deps: DepsMut, _env: &Env, _msg: &MigrateMsg) -> StdResult<()> {
+    let legacy_config = read_legacy_config(deps.storage)?;
+    let denom = legacy_config.reward_denom;
+    store_config(
+        deps.storage,
+        &Config {
+            owner: legacy_config.owner,
+            token_contract: legacy_config.token_contract,
+            reward_denoms: vec![denom.clone()],
+            reward_tokens: vec![],
+            distribution_period: 0,
+            swap_skip_denoms: vec![],
+            swap_dust_threshold: Uint128::new(DEFAULT_SWAP_DUST_THRESHOLD),
+        },
+    )?;
+
+    let legacy_state = read_legacy_state(deps.storage)?;
+    store_state(
+        deps.storage,
+        &State {
+            total_balance: legacy_state.total_balance,
+            reward_indices: vec![RewardIndex {
+                denom: denom.clone(),
+                global_index: legacy_state.global_index,
+                prev_reward_balance: legacy_state.prev_reward_balance,
+                stream: Stream::default(),
+            }],
+            token_indices: vec![],
+        },
+    )?;
+
+    store_legacy_reward_denom(deps.storage, &denom)?;
+
+    Ok(())
+}
+
+/// Credit the rewards received before deposits had to be explicit, which were
+/// detected as the increase of the balance over the accounted rewards.
+/// From then on only DepositRewards credits the global indices.
+fn migrate_balance_diff_rewards(deps: DepsMut, env: &Env, _msg: &MigrateMsg) -> StdResult<()> {
+    let mut state = read_state(deps.storage)?;
+
+    // without any balance the rewards would be credited to nobody,
+    // so they are left unaccounted and can be recovered by the owner
+    if !state.total_balance.is_zero() {
+        for reward_index in state.reward_indices.iter_mut() {
+            let reward_balance = deps
+                .querier
+                .query_balance(env.contract.address.clone(), reward_index.denom.as_str())?
+                .amount;
+            // a balance below the accounted rewards is not credited back to anyone
+            let received_rewards = reward_balance.saturating_sub(reward_index.prev_reward_balance);
+            if received_rewards.is_zero() {
+                continue;
+            }
+
+            reward_index.prev_reward_balance = reward_balance;
+            // global_index += received_rewards / total_balance;
+            reward_index.global_index = decimal_summation_in_256(
+                reward_index.global_index,
+                Decimal::from_ratio(received_rewards, state.total_balance),
+            );
+        }
+        store_state(deps.storage, &state)?;
+    }
+
+    Ok(())
+}
@@ -1,6 +1,14 @@
-use crate::state::{read_config, store_config};
+use crate::state::{
+    read_config, read_pending_owner, read_state, remove_pending_owner, store_config,
+    store_pending_owner, store_state, PendingOwner, RewardIndex, Stream, TokenIndex,
+};
 
-use cosmwasm_std::{attr, Addr, DepsMut, MessageInfo, Response, StdError, StdResult};
+use crate::stream::release_rewards;
+
+use beth::reward::PendingOwnerResponse;
+use cosmwasm_std::{
+    attr, Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult,
+};
 use terra_cosmwasm::TerraMsgWrapper;
 
 pub fn execute_post_initialize(
@@ -22,10 +30,42 @@ pub fn execute_post_initialize(
     Ok(Response::new().add_attributes(vec![attr("action", "post_initialize")]))
 }
 
-pub fn execute_update_config(
+pub fn execute_add_reward_denom(
+    deps: DepsMut,
+    info: MessageInfo,
+    denom: String,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let mut config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if config.reward_denoms.contains(&denom) {
+        return Err(StdError::generic_err(format!(
+            "reward denom {} is already distributed",
+            denom
+        )));
+    }
+
+    // the balance already held is unaccounted until it is deposited
+    let mut state = read_state(deps.storage)?;
+    state.reward_indices.push(RewardIndex::new(denom.clone()));
+    config.reward_denoms.push(denom.clone());
+    store_state(deps.storage, &state)?;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_reward_denom"),
+        attr("denom", denom),
+    ]))
+}
+
+pub fn execute_add_reward_token(
     deps: DepsMut,
     info: MessageInfo,
-    owner: Addr,
+    token_address: Addr,
 ) -> StdResult<Response<TerraMsgWrapper>> {
     let mut config = read_config(deps.storage)?;
     let owner_addr = deps.api.addr_humanize(&config.owner)?;
@@ -34,9 +74,156 @@ pub fn execute_update_config(
         return Err(StdError::generic_err("unauthorized"));
     }
 
-    config.owner = deps.api.addr_canonicalize(owner.as_str())?;
+    let token_raw = deps.api.addr_canonicalize(token_address.as_str())?;
+    if config.reward_tokens.contains(&token_raw) {
+        return Err(StdError::generic_err(format!(
+            "reward token {} is already distributed",
+            token_address
+        )));
+    }
+
+    let mut state = read_state(deps.storage)?;
+    state.token_indices.push(TokenIndex {
+        token: token_raw.clone(),
+        global_index: Decimal::zero(),
+        stream: Stream::default(),
+    });
+    config.reward_tokens.push(token_raw);
+    store_state(deps.storage, &state)?;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "add_reward_token"),
+        attr("token", token_address),
+    ]))
+}
+
+pub fn execute_update_distribution_period(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    distribution_period: u64,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let mut config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    // the rewards streamed so far are released with the former period,
+    // streams already running keep their end
+    let mut state = read_state(deps.storage)?;
+    release_rewards(&mut state, env.block.time.seconds());
+    store_state(deps.storage, &state)?;
+
+    config.distribution_period = distribution_period;
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_distribution_period"),
+        attr("distribution_period", distribution_period.to_string()),
+    ]))
+}
+
+pub fn execute_propose_new_owner(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    new_owner: Addr,
+    expires_in: Option<u64>,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let expires_at = expires_in
+        .map(|expires_in| {
+            env.block
+                .time
+                .seconds()
+                .checked_add(expires_in)
+                .ok_or_else(|| StdError::generic_err("ownership proposal expiry is out of range"))
+        })
+        .transpose()?;
+
+    // a new proposal replaces the pending one
+    store_pending_owner(
+        deps.storage,
+        &PendingOwner {
+            owner: deps.api.addr_canonicalize(new_owner.as_str())?,
+            expires_at,
+        },
+    )?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_new_owner"),
+        attr("new_owner", new_owner),
+    ]))
+}
+
+pub fn execute_cancel_ownership_transfer(
+    deps: DepsMut,
+    info: MessageInfo,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
 
+    if read_pending_owner(deps.storage)?.is_none() {
+        return Err(StdError::generic_err("no ownership transfer is pending"));
+    }
+    remove_pending_owner(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![attr("action", "cancel_ownership_transfer")]))
+}
+
+pub fn execute_accept_ownership(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let pending_owner = read_pending_owner(deps.storage)?
+        .ok_or_else(|| StdError::generic_err("no ownership transfer is pending"))?;
+
+    if info.sender != deps.api.addr_humanize(&pending_owner.owner)? {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(expires_at) = pending_owner.expires_at {
+        if env.block.time.seconds() >= expires_at {
+            return Err(StdError::generic_err("ownership proposal has expired"));
+        }
+    }
+
+    let mut config = read_config(deps.storage)?;
+    config.owner = pending_owner.owner;
     store_config(deps.storage, &config)?;
+    remove_pending_owner(deps.storage);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "accept_ownership"),
+        attr("owner", info.sender),
+    ]))
+}
+
+pub fn query_pending_owner(deps: Deps) -> StdResult<PendingOwnerResponse> {
+    let pending_owner = read_pending_owner(deps.storage)?;
+    let mut res = PendingOwnerResponse {
+        pending_owner: None,
+        expires_at: None,
+    };
+
+    if let Some(pending_owner) = pending_owner {
+        res.pending_owner = Some(deps.api.addr_humanize(&pending_owner.owner)?.to_string());
+        res.expires_at = pending_owner.expires_at;
+    }
 
-    Ok(Response::new().add_attributes(vec![attr("action", "update_config")]))
+    Ok(res)
 }
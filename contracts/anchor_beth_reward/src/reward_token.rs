@@ -0,0 +1,118 @@
+use crate::math::decimal_summation_in_256;
+use crate::state::{read_config, read_holder, read_state, store_state, Holder};
+use crate::stream::{release_rewards, stream_rewards};
+use crate::user::accrue_holder_rewards;
+
+use beth::reward::{
+    AccruedTokenRewardsResponse, Cw20HookMsg, RewardTokenResponse, RewardTokensResponse,
+};
+use cosmwasm_std::{
+    attr, from_binary, Addr, Decimal, Deps, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult, Uint128,
+};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
+use terra_cosmwasm::TerraMsgWrapper;
+
+pub fn execute_receive(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    cw20_msg: Cw20ReceiveMsg,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    match from_binary(&cw20_msg.msg)? {
+        Cw20HookMsg::DepositRewards {} => {
+            execute_deposit_token_rewards(deps, env, info.sender, cw20_msg.amount)
+        }
+    }
+}
+
+/// Stream the amount received into the global index of the reward token
+fn execute_deposit_token_rewards(
+    deps: DepsMut,
+    env: Env,
+    token: Addr,
+    amount: Uint128,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let token_raw = deps.api.addr_canonicalize(token.as_str())?;
+    if !config.reward_tokens.contains(&token_raw) {
+        return Err(StdError::generic_err(format!(
+            "{} is not a reward token",
+            token
+        )));
+    }
+
+    let mut state = read_state(deps.storage)?;
+    // the tokens would be stuck as nobody could claim them
+    if state.total_balance.is_zero() {
+        return Err(StdError::generic_err("No balance to distribute rewards to"));
+    }
+
+    let now = env.block.time.seconds();
+    release_rewards(&mut state, now);
+    let token_index = state
+        .token_indices
+        .iter_mut()
+        .find(|token_index| token_index.token == token_raw)
+        .ok_or_else(|| StdError::generic_err(format!("{} is not a reward token", token)))?;
+    let credited = stream_rewards(
+        &mut token_index.stream,
+        amount,
+        now,
+        config.distribution_period,
+    );
+    // global_index += credited_rewards / total_balance;
+    token_index.global_index = decimal_summation_in_256(
+        token_index.global_index,
+        Decimal::from_ratio(credited, state.total_balance),
+    );
+    store_state(deps.storage, &state)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deposit_token_rewards"),
+        attr("token", token),
+        attr("amount", amount),
+    ]))
+}
+
+pub fn query_reward_tokens(deps: Deps) -> StdResult<RewardTokensResponse> {
+    let state = read_state(deps.storage)?;
+
+    Ok(RewardTokensResponse {
+        reward_tokens: state
+            .token_indices
+            .into_iter()
+            .map(|token_index| {
+                Ok(RewardTokenResponse {
+                    token_address: deps.api.addr_humanize(&token_index.token)?.to_string(),
+                    global_index: token_index.global_index,
+                })
+            })
+            .collect::<StdResult<Vec<_>>>()?,
+    })
+}
+
+pub fn query_accrued_token_rewards(
+    deps: Deps,
+    env: Env,
+    address: Addr,
+) -> StdResult<AccruedTokenRewardsResponse> {
+    let mut state = read_state(deps.storage)?;
+    release_rewards(&mut state, env.block.time.seconds());
+    let mut holder: Holder =
+        read_holder(deps.storage, &deps.api.addr_canonicalize(address.as_str())?)?;
+    accrue_holder_rewards(&state, &mut holder)?;
+
+    let mut rewards = vec![];
+    for token_index in holder.token_indices {
+        let amount = token_index.pending_rewards * Uint128::new(1);
+        if !amount.is_zero() {
+            rewards.push(Cw20Coin {
+                address: deps.api.addr_humanize(&token_index.token)?.to_string(),
+                amount,
+            });
+        }
+    }
+
+    Ok(AccruedTokenRewardsResponse { rewards })
+}
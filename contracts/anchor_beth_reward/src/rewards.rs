@@ -0,0 +1,137 @@
+use crate::math::decimal_summation_in_256;
+use crate::state::{read_config, read_state, store_state, State};
+use crate::stream::{release_rewards, stream_rewards};
+
+use beth::deduct_tax;
+use cosmwasm_std::{
+    attr, Addr, BankMsg, Coin, CosmosMsg, Decimal, DepsMut, Env, MessageInfo, Response, StdError,
+    StdResult,
+};
+use terra_cosmwasm::TerraMsgWrapper;
+
+/// Stream the coins sent along into the global index of each reward denom
+pub fn execute_deposit_rewards(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    if info.funds.is_empty() {
+        return Err(StdError::generic_err("No rewards sent"));
+    }
+
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+    // the coins would be stuck as nobody could claim them
+    if state.total_balance.is_zero() {
+        return Err(StdError::generic_err("No balance to distribute rewards to"));
+    }
+
+    let now = env.block.time.seconds();
+    release_rewards(&mut state, now);
+    for coin in info.funds.iter() {
+        credit_rewards(&mut state, coin, now, config.distribution_period)?;
+    }
+    store_state(deps.storage, &state)?;
+
+    let rewards = info
+        .funds
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "deposit_rewards"),
+        attr("rewards", rewards),
+    ]))
+}
+
+/// Account the reward coins received and stream them into the global index,
+/// once the rewards streamed so far have been released
+pub(crate) fn credit_rewards(
+    state: &mut State,
+    coin: &Coin,
+    now: u64,
+    distribution_period: u64,
+) -> StdResult<()> {
+    let total_balance = state.total_balance;
+    let reward_index = state
+        .reward_indices
+        .iter_mut()
+        .find(|reward_index| reward_index.denom == coin.denom)
+        .ok_or_else(|| StdError::generic_err(format!("{} is not a reward denom", coin.denom)))?;
+
+    reward_index.prev_reward_balance += coin.amount;
+    let credited = stream_rewards(
+        &mut reward_index.stream,
+        coin.amount,
+        now,
+        distribution_period,
+    );
+    // global_index += credited_rewards / total_balance;
+    reward_index.global_index = decimal_summation_in_256(
+        reward_index.global_index,
+        Decimal::from_ratio(credited, total_balance),
+    );
+
+    Ok(())
+}
+
+/// Send the coins held which are not owed to the holders, e.g. sent by accident
+pub fn execute_recover_unaccounted(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    recipient: Addr,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let state = read_state(deps.storage)?;
+    let mut unaccounted: Vec<Coin> = vec![];
+    for coin in deps.querier.query_all_balances(env.contract.address)? {
+        let accounted = state
+            .reward_indices
+            .iter()
+            .find(|reward_index| reward_index.denom == coin.denom)
+            .map(|reward_index| reward_index.prev_reward_balance)
+            .unwrap_or_default();
+        let amount = coin.amount.saturating_sub(accounted);
+        if !amount.is_zero() {
+            unaccounted.push(Coin {
+                denom: coin.denom,
+                amount,
+            });
+        }
+    }
+
+    if unaccounted.is_empty() {
+        return Err(StdError::generic_err("No unaccounted funds to recover"));
+    }
+
+    let recovered = unaccounted
+        .iter()
+        .map(|coin| coin.to_string())
+        .collect::<Vec<_>>()
+        .join(",");
+    let bank_msg: CosmosMsg<TerraMsgWrapper> = BankMsg::Send {
+        to_address: recipient.to_string(),
+        amount: unaccounted
+            .into_iter()
+            .map(|coin| deduct_tax(deps.as_ref(), coin))
+            .collect::<StdResult<Vec<_>>>()?,
+    }
+    .into();
+
+    Ok(Response::new()
+        .add_attributes(vec![
+            attr("action", "recover_unaccounted"),
+            attr("recipient", recipient),
+            attr("amount", recovered),
+        ])
+        .add_message(bank_msg))
+}
@@ -1,14 +1,22 @@
-use cosmwasm_std::{CanonicalAddr, Decimal, Deps, Order, StdResult, Storage, Uint128};
-use cosmwasm_storage::{bucket, bucket_read, singleton, singleton_read, ReadonlyBucket};
+use cosmwasm_std::{from_slice, CanonicalAddr, Decimal, Deps, Order, StdResult, Storage, Uint128};
+use cosmwasm_storage::{
+    bucket, bucket_read, prefixed_read, singleton, singleton_read, ReadonlyBucket,
+};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use beth::reward::HolderResponse;
+use beth::reward::{
+    ExecuteMsg, HolderIndexResponse, HolderResponse, HolderTokenIndexResponse, QueuedActionResponse,
+};
 
 pub static KEY_CONFIG: &[u8] = b"config";
 pub static KEY_STATE: &[u8] = b"state";
+pub static KEY_PENDING_OWNER: &[u8] = b"pending_owner";
+pub static KEY_TIMELOCK: &[u8] = b"timelock";
+pub static KEY_LEGACY_REWARD_DENOM: &[u8] = b"legacy_reward_denom";
 
 pub static PREFIX_HOLDERS: &[u8] = b"holders";
+pub static PREFIX_QUEUED_ACTIONS: &[u8] = b"queued_actions";
 pub static KEY_CONTRACT_ADDR: &[u8] = b"contract_addr";
 
 pub fn read_contract_addr(storage: &dyn Storage) -> StdResult<CanonicalAddr> {
@@ -26,7 +34,25 @@ pub fn store_contract_addr(
 pub struct Config {
     pub owner: CanonicalAddr,
     pub token_contract: Option<CanonicalAddr>,
-    pub reward_denom: String,
+    pub reward_denoms: Vec<String>,
+    /// cw20 tokens distributed through the receive hook
+    #[serde(default)]
+    pub reward_tokens: Vec<CanonicalAddr>,
+    /// seconds over which each deposit is released to the holders, instantly if zero
+    #[serde(default)]
+    pub distribution_period: u64,
+    /// native denoms which are never swapped to the reward denom
+    #[serde(default)]
+    pub swap_skip_denoms: Vec<String>,
+    /// balances up to this amount are not worth swapping
+    #[serde(default = "default_swap_dust_threshold")]
+    pub swap_dust_threshold: Uint128,
+}
+
+pub const DEFAULT_SWAP_DUST_THRESHOLD: u128 = 10000;
+
+fn default_swap_dust_threshold() -> Uint128 {
+    Uint128::new(DEFAULT_SWAP_DUST_THRESHOLD)
 }
 
 pub fn store_config(storage: &mut dyn Storage, config: &Config) -> StdResult<()> {
@@ -37,11 +63,119 @@ pub fn read_config(storage: &dyn Storage) -> StdResult<Config> {
     singleton_read(storage, KEY_CONFIG).load()
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwner {
+    pub owner: CanonicalAddr,
+    /// time in seconds after which the proposal can no longer be accepted
+    pub expires_at: Option<u64>,
+}
+
+pub fn store_pending_owner(
+    storage: &mut dyn Storage,
+    pending_owner: &PendingOwner,
+) -> StdResult<()> {
+    singleton(storage, KEY_PENDING_OWNER).save(pending_owner)
+}
+
+pub fn read_pending_owner(storage: &dyn Storage) -> StdResult<Option<PendingOwner>> {
+    singleton_read(storage, KEY_PENDING_OWNER).may_load()
+}
+
+pub fn remove_pending_owner(storage: &mut dyn Storage) {
+    singleton::<PendingOwner>(storage, KEY_PENDING_OWNER).remove()
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Timelock {
+    /// seconds between the proposal and the execution of an owner operation
+    pub delay: u64,
+    pub next_action_id: u64,
+}
+
+pub fn store_timelock(storage: &mut dyn Storage, timelock: &Timelock) -> StdResult<()> {
+    singleton(storage, KEY_TIMELOCK).save(timelock)
+}
+
+pub fn read_timelock(storage: &dyn Storage) -> StdResult<Timelock> {
+    Ok(singleton_read(storage, KEY_TIMELOCK)
+        .may_load()?
+        .unwrap_or_default())
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedAction {
+    pub msg: ExecuteMsg,
+    /// time in seconds from which the operation can be executed
+    pub eta: u64,
+    /// owner that queued the operation; it only runs while they still own the contract
+    pub proposer: CanonicalAddr,
+}
+
+// This is similar to HashMap<action id, QueuedAction>
+pub fn store_queued_action(
+    storage: &mut dyn Storage,
+    id: u64,
+    action: &QueuedAction,
+) -> StdResult<()> {
+    bucket(storage, PREFIX_QUEUED_ACTIONS).save(&id.to_be_bytes(), action)
+}
+
+pub fn read_queued_action(storage: &dyn Storage, id: u64) -> StdResult<Option<QueuedAction>> {
+    bucket_read(storage, PREFIX_QUEUED_ACTIONS).may_load(&id.to_be_bytes())
+}
+
+pub fn remove_queued_action(storage: &mut dyn Storage, id: u64) {
+    bucket::<QueuedAction>(storage, PREFIX_QUEUED_ACTIONS).remove(&id.to_be_bytes())
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct State {
-    pub global_index: Decimal,
     pub total_balance: Uint128,
+    /// global index of each reward denom
+    pub reward_indices: Vec<RewardIndex>,
+    /// global index of each cw20 reward token
+    #[serde(default)]
+    pub token_indices: Vec<TokenIndex>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardIndex {
+    pub denom: String,
+    pub global_index: Decimal,
+    /// rewards deposited and not claimed yet
     pub prev_reward_balance: Uint128,
+    #[serde(default)]
+    pub stream: Stream,
+}
+
+impl RewardIndex {
+    pub fn new(denom: String) -> Self {
+        RewardIndex {
+            denom,
+            global_index: Decimal::zero(),
+            prev_reward_balance: Uint128::zero(),
+            stream: Stream::default(),
+        }
+    }
+}
+
+/// Deposited rewards being released linearly into the global index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema, Default)]
+pub struct Stream {
+    /// deposits not fully released yet, each over its own distribution period
+    pub deposits: Vec<StreamDeposit>,
+    /// time in seconds of the last release
+    pub last_release: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StreamDeposit {
+    /// rewards of the deposit not released yet
+    pub undistributed: Uint128,
+    /// rewards of the deposit released per second
+    pub emission_rate: Decimal,
+    /// time in seconds at which the whole deposit has been released
+    pub end: u64,
 }
 
 pub fn store_state(storage: &mut dyn Storage, state: &State) -> StdResult<()> {
@@ -52,13 +186,101 @@ pub fn read_state(storage: &dyn Storage) -> StdResult<State> {
     singleton_read(storage, KEY_STATE).load()
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenIndex {
+    pub token: CanonicalAddr,
+    pub global_index: Decimal,
+    #[serde(default)]
+    pub stream: Stream,
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct Holder {
     pub balance: Uint128,
+    /// index and pending rewards of each reward denom the holder has accrued
+    pub reward_indices: Vec<HolderIndex>,
+    /// index and pending rewards of each cw20 reward token the holder has accrued
+    #[serde(default)]
+    pub token_indices: Vec<HolderTokenIndex>,
+    /// where the rewards are sent when claimed without a recipient
+    #[serde(default)]
+    pub withdraw_address: Option<CanonicalAddr>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderIndex {
+    pub denom: String,
     pub index: Decimal,
     pub pending_rewards: Decimal,
 }
 
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderTokenIndex {
+    pub token: CanonicalAddr,
+    pub index: Decimal,
+    pub pending_rewards: Decimal,
+}
+
+impl HolderTokenIndex {
+    pub fn into_response(self, deps: Deps) -> StdResult<HolderTokenIndexResponse> {
+        Ok(HolderTokenIndexResponse {
+            token_address: deps.api.addr_humanize(&self.token)?.to_string(),
+            index: self.index,
+            pending_rewards: self.pending_rewards,
+        })
+    }
+}
+
+impl HolderIndex {
+    pub fn into_response(self) -> HolderIndexResponse {
+        HolderIndexResponse {
+            denom: self.denom,
+            index: self.index,
+            pending_rewards: self.pending_rewards,
+        }
+    }
+}
+
+impl Holder {
+    /// index of the given denom, starting from zero like the global index does
+    pub fn reward_index(&mut self, denom: &str) -> &mut HolderIndex {
+        match self
+            .reward_indices
+            .iter()
+            .position(|item| item.denom == denom)
+        {
+            Some(position) => &mut self.reward_indices[position],
+            None => {
+                self.reward_indices.push(HolderIndex {
+                    denom: denom.to_string(),
+                    index: Decimal::zero(),
+                    pending_rewards: Decimal::zero(),
+                });
+                self.reward_indices.last_mut().unwrap()
+            }
+        }
+    }
+
+    /// index of the given cw20 reward token, starting from zero like the global index does
+    pub fn token_index(&mut self, token: &CanonicalAddr) -> &mut HolderTokenIndex {
+        match self
+            .token_indices
+            .iter()
+            .position(|item| &item.token == token)
+        {
+            Some(position) => &mut self.token_indices[position],
+            None => {
+                self.token_indices.push(HolderTokenIndex {
+                    token: token.clone(),
+                    index: Decimal::zero(),
+                    pending_rewards: Decimal::zero(),
+                });
+                self.token_indices.last_mut().unwrap()
+            }
+        }
+    }
+}
+
 // This is similar to HashMap<holder's address, Hodler>
 pub fn store_holder(
     storage: &mut dyn Storage,
@@ -69,18 +291,90 @@ pub fn store_holder(
 }
 
 pub fn read_holder(storage: &dyn Storage, holder_address: &CanonicalAddr) -> StdResult<Holder> {
-    let res: Option<Holder> =
-        bucket_read(storage, PREFIX_HOLDERS).may_load(holder_address.as_slice())?;
-    match res {
-        Some(holder) => Ok(holder),
+    match prefixed_read(storage, PREFIX_HOLDERS).get(holder_address.as_slice()) {
+        Some(data) => parse_holder(storage, &data),
         None => Ok(Holder {
             balance: Uint128::zero(),
-            index: Decimal::zero(),
-            pending_rewards: Decimal::zero(),
+            reward_indices: vec![],
+            token_indices: vec![],
+            withdraw_address: None,
         }),
     }
 }
 
+/// Config of the instances deployed before multiple reward denoms
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyConfig {
+    pub owner: CanonicalAddr,
+    pub token_contract: Option<CanonicalAddr>,
+    pub reward_denom: String,
+}
+
+/// State of the instances deployed before multiple reward denoms
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyState {
+    pub global_index: Decimal,
+    pub total_balance: Uint128,
+    pub prev_reward_balance: Uint128,
+}
+
+/// Holder of the instances deployed before multiple reward denoms
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct LegacyHolder {
+    pub balance: Uint128,
+    pub index: Decimal,
+    pub pending_rewards: Decimal,
+}
+
+pub fn read_legacy_config(storage: &dyn Storage) -> StdResult<LegacyConfig> {
+    singleton_read(storage, KEY_CONFIG).load()
+}
+
+pub fn read_legacy_state(storage: &dyn Storage) -> StdResult<LegacyState> {
+    singleton_read(storage, KEY_STATE).load()
+}
+
+impl LegacyHolder {
+    pub fn into_holder(self, denom: String) -> Holder {
+        Holder {
+            balance: self.balance,
+            reward_indices: vec![HolderIndex {
+                denom,
+                index: self.index,
+                pending_rewards: self.pending_rewards,
+            }],
+            token_indices: vec![],
+            withdraw_address: None,
+        }
+    }
+}
+
+/// reward denom the holders left in the legacy layout have accrued
+pub fn store_legacy_reward_denom(storage: &mut dyn Storage, denom: &str) -> StdResult<()> {
+    singleton(storage, KEY_LEGACY_REWARD_DENOM).save(&denom.to_string())
+}
+
+pub fn read_legacy_reward_denom(storage: &dyn Storage) -> StdResult<Option<String>> {
+    singleton_read(storage, KEY_LEGACY_REWARD_DENOM).may_load()
+}
+
+/// Holders are not rewritten by the migration, as their number is unbounded.
+/// The ones still in the legacy layout are converted when read, and stored
+/// in the current layout the next time they are updated.
+fn parse_holder(storage: &dyn Storage, data: &[u8]) -> StdResult<Holder> {
+    let err = match from_slice::<Holder>(data) {
+        Ok(holder) => return Ok(holder),
+        Err(err) => err,
+    };
+    match (
+        from_slice::<LegacyHolder>(data),
+        read_legacy_reward_denom(storage)?,
+    ) {
+        (Ok(legacy_holder), Some(denom)) => Ok(legacy_holder.into_holder(denom)),
+        _ => Err(err),
+    }
+}
+
 // settings for pagination
 const MAX_LIMIT: u32 = 30;
 const DEFAULT_LIMIT: u32 = 10;
@@ -89,22 +383,60 @@ pub fn read_holders(
     start_after: Option<CanonicalAddr>,
     limit: Option<u32>,
 ) -> StdResult<Vec<HolderResponse>> {
-    let holder_bucket: ReadonlyBucket<Holder> = bucket_read(deps.storage, PREFIX_HOLDERS);
+    let holder_storage = prefixed_read(deps.storage, PREFIX_HOLDERS);
 
     let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
     let start = calc_range_start(start_after);
 
-    holder_bucket
+    holder_storage
         .range(start.as_deref(), None, Order::Ascending)
         .take(limit)
-        .map(|elem| {
-            let (k, v) = elem?;
+        .map(|(k, data)| {
+            let v = parse_holder(deps.storage, &data)?;
             let address = deps.api.addr_humanize(&CanonicalAddr::from(k))?.to_string();
             Ok(HolderResponse {
                 address,
                 balance: v.balance,
-                index: v.index,
-                pending_rewards: v.pending_rewards,
+                reward_indices: v
+                    .reward_indices
+                    .into_iter()
+                    .map(HolderIndex::into_response)
+                    .collect(),
+                token_indices: v
+                    .token_indices
+                    .into_iter()
+                    .map(|token_index| token_index.into_response(deps))
+                    .collect::<StdResult<Vec<_>>>()?,
+                withdraw_address: v
+                    .withdraw_address
+                    .map(|addr| deps.api.addr_humanize(&addr).map(|addr| addr.to_string()))
+                    .transpose()?,
+            })
+        })
+        .collect()
+}
+
+pub fn read_queued_actions(
+    storage: &dyn Storage,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<Vec<QueuedActionResponse>> {
+    let action_bucket: ReadonlyBucket<QueuedAction> = bucket_read(storage, PREFIX_QUEUED_ACTIONS);
+
+    let limit = limit.unwrap_or(DEFAULT_LIMIT).min(MAX_LIMIT) as usize;
+    let start = start_after.map(|id| id.saturating_add(1).to_be_bytes().to_vec());
+
+    action_bucket
+        .range(start.as_deref(), None, Order::Ascending)
+        .take(limit)
+        .map(|elem| {
+            let (k, v) = elem?;
+            let mut id = [0u8; 8];
+            id.copy_from_slice(&k);
+            Ok(QueuedActionResponse {
+                id: u64::from_be_bytes(id),
+                msg: v.msg,
+                eta: v.eta,
             })
         })
         .collect()
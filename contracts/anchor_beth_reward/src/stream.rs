@@ -0,0 +1,139 @@
+use crate::math::decimal_summation_in_256;
+use crate::state::{read_config, read_state, State, Stream, StreamDeposit};
+
+use beth::reward::{DistributionResponse, RewardStreamResponse};
+use cosmwasm_std::{Decimal, Deps, Env, StdResult, Uint128};
+
+/// Add deposited rewards to the stream, which releases them over the distribution
+/// period without changing the schedule of the earlier deposits.
+/// Returns the rewards to credit right away.
+pub(crate) fn stream_rewards(
+    stream: &mut Stream,
+    amount: Uint128,
+    now: u64,
+    period: u64,
+) -> Uint128 {
+    if period == 0 {
+        return amount;
+    }
+
+    stream.deposits.push(StreamDeposit {
+        undistributed: amount,
+        emission_rate: Decimal::from_ratio(amount, period),
+        end: now + period,
+    });
+    stream.last_release = now;
+
+    Uint128::zero()
+}
+
+/// Release the rewards streamed since the last release
+fn release(stream: &mut Stream, now: u64) -> Uint128 {
+    if now <= stream.last_release {
+        return Uint128::zero();
+    }
+
+    let elapsed = now - stream.last_release;
+    let mut released = Uint128::zero();
+    for deposit in stream.deposits.iter_mut() {
+        let amount = if now >= deposit.end {
+            deposit.undistributed
+        } else {
+            std::cmp::min(
+                deposit.emission_rate * Uint128::from(elapsed),
+                deposit.undistributed,
+            )
+        };
+        deposit.undistributed -= amount;
+        released += amount;
+    }
+
+    stream
+        .deposits
+        .retain(|deposit| !deposit.undistributed.is_zero());
+    stream.last_release = now;
+
+    released
+}
+
+/// Credit the rewards streamed until now to the global indices.
+/// Streams are paused while there is no balance to credit.
+pub(crate) fn release_rewards(state: &mut State, now: u64) {
+    let total_balance = state.total_balance;
+    let indices = state
+        .reward_indices
+        .iter_mut()
+        .map(|reward_index| (&mut reward_index.global_index, &mut reward_index.stream))
+        .chain(
+            state
+                .token_indices
+                .iter_mut()
+                .map(|token_index| (&mut token_index.global_index, &mut token_index.stream)),
+        );
+
+    for (global_index, stream) in indices {
+        if total_balance.is_zero() {
+            if !stream.deposits.is_empty() && now > stream.last_release {
+                for deposit in stream.deposits.iter_mut() {
+                    deposit.end += now - stream.last_release;
+                }
+                stream.last_release = now;
+            }
+            continue;
+        }
+
+        let released = release(stream, now);
+        if !released.is_zero() {
+            // global_index += released_rewards / total_balance;
+            *global_index = decimal_summation_in_256(
+                *global_index,
+                Decimal::from_ratio(released, total_balance),
+            );
+        }
+    }
+}
+
+pub fn query_distribution(deps: Deps, env: Env) -> StdResult<DistributionResponse> {
+    let config = read_config(deps.storage)?;
+    let mut state = read_state(deps.storage)?;
+    release_rewards(&mut state, env.block.time.seconds());
+
+    let mut streams: Vec<RewardStreamResponse> = state
+        .reward_indices
+        .into_iter()
+        .map(|reward_index| stream_response(reward_index.denom, reward_index.stream))
+        .collect();
+    for token_index in state.token_indices {
+        let token = deps.api.addr_humanize(&token_index.token)?.to_string();
+        streams.push(stream_response(token, token_index.stream));
+    }
+
+    Ok(DistributionResponse {
+        distribution_period: config.distribution_period,
+        streams,
+    })
+}
+
+/// Totals of the deposits being streamed, which are all released at `end`
+fn stream_response(reward: String, stream: Stream) -> RewardStreamResponse {
+    RewardStreamResponse {
+        reward,
+        emission_rate: stream
+            .deposits
+            .iter()
+            .fold(Decimal::zero(), |rate, deposit| {
+                rate + deposit.emission_rate
+            }),
+        undistributed: stream
+            .deposits
+            .iter()
+            .map(|deposit| deposit.undistributed)
+            .sum(),
+        end: stream
+            .deposits
+            .iter()
+            .map(|deposit| deposit.end)
+            .max()
+            .unwrap_or_default(),
+    }
+}
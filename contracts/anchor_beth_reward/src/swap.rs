@@ -0,0 +1,126 @@
+use crate::rewards::credit_rewards;
+use crate::state::{read_config, read_state, store_config, store_state};
+use crate::stream::release_rewards;
+
+use beth::reward::ExecuteMsg;
+use cosmwasm_std::{
+    attr, to_binary, Coin, DepsMut, Env, MessageInfo, Response, StdError, StdResult, Uint128,
+    WasmMsg,
+};
+use terra_cosmwasm::{create_swap_msg, TerraMsgWrapper};
+
+/// Swap the native balances which are not rewards to the first reward denom,
+/// which is credited to the holders once the swaps are done
+pub fn execute_swap(deps: DepsMut, env: Env) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let reward_denom = config
+        .reward_denoms
+        .first()
+        .ok_or_else(|| StdError::generic_err("No reward denom to swap to"))?;
+
+    let balances = deps
+        .querier
+        .query_all_balances(env.contract.address.clone())?;
+    let prev_reward_balance = balances
+        .iter()
+        .find(|coin| &coin.denom == reward_denom)
+        .map(|coin| coin.amount)
+        .unwrap_or_default();
+
+    let mut res = Response::new();
+    let mut swapped: Vec<String> = vec![];
+    for coin in balances {
+        if config.reward_denoms.contains(&coin.denom)
+            || config.swap_skip_denoms.contains(&coin.denom)
+            || coin.amount <= config.swap_dust_threshold
+        {
+            continue;
+        }
+
+        swapped.push(coin.to_string());
+        res = res.add_message(create_swap_msg(coin, reward_denom.to_string()));
+    }
+
+    if swapped.is_empty() {
+        return Err(StdError::generic_err("No balance to swap"));
+    }
+
+    Ok(res
+        .add_message(WasmMsg::Execute {
+            contract_addr: env.contract.address.to_string(),
+            msg: to_binary(&ExecuteMsg::UpdateGlobalIndex {
+                prev_reward_balance,
+            })?,
+            funds: vec![],
+        })
+        .add_attributes(vec![
+            attr("action", "swap"),
+            attr("swapped", swapped.join(",")),
+        ]))
+}
+
+/// Credit the reward denom received from the swaps, i.e. the balance above the one
+/// recorded before swapping; any unaccounted reward denom held before stays recoverable
+pub fn execute_update_global_index(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    prev_reward_balance: Uint128,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    if info.sender != env.contract.address {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    let config = read_config(deps.storage)?;
+    let reward_denom = config
+        .reward_denoms
+        .first()
+        .ok_or_else(|| StdError::generic_err("No reward denom to swap to"))?;
+    let reward_balance = deps
+        .querier
+        .query_balance(env.contract.address, reward_denom.as_str())?
+        .amount;
+    let amount = reward_balance.saturating_sub(prev_reward_balance);
+
+    // without any balance the rewards are left unaccounted
+    let mut state = read_state(deps.storage)?;
+    if !amount.is_zero() && !state.total_balance.is_zero() {
+        let now = env.block.time.seconds();
+        release_rewards(&mut state, now);
+        let coin = Coin {
+            denom: reward_denom.to_string(),
+            amount,
+        };
+        credit_rewards(&mut state, &coin, now, config.distribution_period)?;
+        store_state(deps.storage, &state)?;
+    }
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "update_global_index"),
+        attr("amount", amount),
+    ]))
+}
+
+pub fn execute_update_swap_config(
+    deps: DepsMut,
+    info: MessageInfo,
+    skip_denoms: Option<Vec<String>>,
+    dust_threshold: Option<Uint128>,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let mut config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if let Some(skip_denoms) = skip_denoms {
+        config.swap_skip_denoms = skip_denoms;
+    }
+    if let Some(dust_threshold) = dust_threshold {
+        config.swap_dust_threshold = dust_threshold;
+    }
+    store_config(deps.storage, &config)?;
+
+    Ok(Response::new().add_attributes(vec![attr("action", "update_swap_config")]))
+}
@@ -1,23 +1,64 @@
-use cosmwasm_std::testing::{mock_env, mock_info};
-use cosmwasm_std::{from_binary, BankMsg, Coin, CosmosMsg, Decimal, StdError, SubMsg, Uint128};
+use cosmwasm_std::testing::{mock_env, mock_info, MockApi, MockStorage, MOCK_CONTRACT_ADDR};
+use cosmwasm_std::{
+    from_binary, to_binary, Api, BankMsg, Coin, CosmosMsg, Decimal, OwnedDeps, StdError, SubMsg,
+    Uint128, WasmMsg,
+};
+use cw20::{Cw20Coin, Cw20ExecuteMsg, Cw20ReceiveMsg};
+use terra_cosmwasm::create_swap_msg;
 
-use crate::contract::{execute, instantiate, query};
+use crate::contract::{execute, instantiate, migrate, query};
 use crate::math::{decimal_multiplication_in_256, decimal_subtraction_in_256};
-use crate::testing::mock_querier::mock_dependencies;
+use crate::state::{
+    Holder, HolderIndex, LegacyConfig, LegacyHolder, LegacyState, KEY_CONFIG, KEY_STATE,
+    PREFIX_HOLDERS,
+};
+use crate::testing::mock_querier::{mock_dependencies, WasmMockQuerier};
 use beth::reward::{
-    ConfigResponse, ExecuteMsg, HolderResponse, HoldersResponse, InstantiateMsg, QueryMsg,
-    StateResponse,
+    AccruedRewardsResponse, AccruedTokenRewardsResponse, ConfigResponse, Cw20HookMsg,
+    DistributionResponse, ExecuteMsg, HolderIndexResponse, HolderResponse, HoldersResponse,
+    InstantiateMsg, MigrateMsg, PendingOwnerResponse, QueryMsg, QueuedActionResponse,
+    QueuedActionsResponse, RewardIndexResponse, RewardStreamResponse, RewardTokenResponse,
+    RewardTokensResponse, StateResponse,
 };
+use cosmwasm_storage::{bucket, bucket_read, singleton};
 use std::str::FromStr;
 
 const DEFAULT_REWARD_DENOM: &str = "uusd";
 const MOCK_OWNER_ADDR: &str = "owner0000";
 const MOCK_TOKEN_CONTRACT_ADDR: &str = "token0000";
+const MOCK_REWARD_TOKEN_ADDR: &str = "reward_token0000";
 
 fn default_init() -> InstantiateMsg {
     InstantiateMsg {
         owner: MOCK_OWNER_ADDR.to_string(),
-        reward_denom: DEFAULT_REWARD_DENOM.to_string(),
+        reward_denoms: vec![DEFAULT_REWARD_DENOM.to_string()],
+    }
+}
+
+fn deposit_rewards(deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, rewards: &[Coin]) {
+    let info = mock_info("depositor0000", rewards);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        info,
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap();
+}
+
+fn holder_indices(index: Decimal, pending_rewards: Decimal) -> Vec<HolderIndexResponse> {
+    vec![HolderIndexResponse {
+        denom: DEFAULT_REWARD_DENOM.to_string(),
+        index,
+        pending_rewards,
+    }]
+}
+
+fn reward_index(global_index: Decimal, prev_reward_balance: Uint128) -> RewardIndexResponse {
+    RewardIndexResponse {
+        denom: DEFAULT_REWARD_DENOM.to_string(),
+        global_index,
+        prev_reward_balance,
     }
 }
 
@@ -45,7 +86,11 @@ fn proper_init() {
         ConfigResponse {
             owner: MOCK_OWNER_ADDR.to_string(),
             token_contract: Some(MOCK_TOKEN_CONTRACT_ADDR.to_string()),
-            reward_denom: DEFAULT_REWARD_DENOM.to_string(),
+            reward_denoms: vec![DEFAULT_REWARD_DENOM.to_string()],
+            reward_tokens: vec![],
+            distribution_period: 0,
+            swap_skip_denoms: vec![],
+            swap_dust_threshold: Uint128::new(10000u128),
         }
     );
 
@@ -54,9 +99,8 @@ fn proper_init() {
     assert_eq!(
         state_response,
         StateResponse {
-            global_index: Decimal::zero(),
             total_balance: Uint128::new(0u128),
-            prev_reward_balance: Uint128::zero()
+            reward_indices: vec![reward_index(Decimal::zero(), Uint128::zero())],
         }
     );
 }
@@ -92,6 +136,13 @@ fn increase_balance() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }],
+    );
 
     let res = query(
         deps.as_ref(),
@@ -107,8 +158,9 @@ fn increase_balance() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(100u128),
-            index: Decimal::zero(),
-            pending_rewards: Decimal::zero(),
+            reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 
@@ -133,8 +185,9 @@ fn increase_balance() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(200u128),
-            index: Decimal::one(),
-            pending_rewards: Decimal::from_str("100").unwrap(),
+            reward_indices: holder_indices(Decimal::one(), Decimal::from_str("100").unwrap()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 }
@@ -170,6 +223,13 @@ fn increase_balance_with_decimals() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100000u128),
+        }],
+    );
 
     let res = query(
         deps.as_ref(),
@@ -185,8 +245,9 @@ fn increase_balance_with_decimals() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(11u128),
-            index: Decimal::zero(),
-            pending_rewards: Decimal::zero(),
+            reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 
@@ -212,15 +273,16 @@ fn increase_balance_with_decimals() {
     );
     let user_pend_reward = decimal_multiplication_in_256(
         Decimal::from_str("11").unwrap(),
-        decimal_subtraction_in_256(holder_response.index, Decimal::zero()),
+        decimal_subtraction_in_256(holder_response.reward_indices[0].index, Decimal::zero()),
     );
     assert_eq!(
         holder_response,
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(21u128),
-            index,
-            pending_rewards: user_pend_reward,
+            reward_indices: holder_indices(index, user_pend_reward),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 }
@@ -273,6 +335,13 @@ fn decrease_balance() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }],
+    );
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     let msg = ExecuteMsg::DecreaseBalance {
@@ -295,8 +364,9 @@ fn decrease_balance() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::zero(),
-            index: Decimal::one(),
-            pending_rewards: Decimal::from_str("100").unwrap(),
+            reward_indices: holder_indices(Decimal::one(), Decimal::from_str("100").unwrap()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 }
@@ -325,6 +395,13 @@ fn claim_rewards() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }],
+    );
 
     let res = query(
         deps.as_ref(),
@@ -340,8 +417,9 @@ fn claim_rewards() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(100u128),
-            index: Decimal::zero(),
-            pending_rewards: Decimal::zero(),
+            reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 
@@ -359,6 +437,13 @@ fn claim_rewards() {
         }))]
     );
 
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }],
+    );
     let msg = ExecuteMsg::ClaimRewards {
         recipient: Some("addr0001".to_string()),
     };
@@ -400,6 +485,13 @@ fn claim_rewards_with_decimals() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(99999u128),
+        }],
+    );
 
     let res = query(
         deps.as_ref(),
@@ -415,8 +507,9 @@ fn claim_rewards_with_decimals() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(11u128),
-            index: Decimal::zero(),
-            pending_rewards: Decimal::zero(),
+            reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 
@@ -452,8 +545,12 @@ fn claim_rewards_with_decimals() {
         HolderResponse {
             address: "addr0000".to_string(),
             balance: Uint128::from(11u128),
-            index,
-            pending_rewards: Decimal::from_str("0.999999999999999991").unwrap(),
+            reward_indices: holder_indices(
+                index,
+                Decimal::from_str("0.999999999999999991").unwrap()
+            ),
+            token_indices: vec![],
+            withdraw_address: None,
         }
     );
 
@@ -462,9 +559,8 @@ fn claim_rewards_with_decimals() {
     assert_eq!(
         state_response,
         StateResponse {
-            global_index: index,
             total_balance: Uint128::new(11u128),
-            prev_reward_balance: Uint128::new(1)
+            reward_indices: vec![reward_index(index, Uint128::new(1))],
         }
     );
 }
@@ -494,6 +590,13 @@ fn query_holders() {
 
     let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
     execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }],
+    );
 
     let msg = ExecuteMsg::IncreaseBalance {
         address: String::from("addr0001"),
@@ -525,20 +628,23 @@ fn query_holders() {
                 HolderResponse {
                     address: String::from("addr0000"),
                     balance: Uint128::from(100u128),
-                    index: Decimal::zero(),
-                    pending_rewards: Decimal::zero(),
+                    reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+                    token_indices: vec![],
+                    withdraw_address: None,
                 },
                 HolderResponse {
                     address: String::from("addr0001"),
                     balance: Uint128::from(200u128),
-                    index: Decimal::one(),
-                    pending_rewards: Decimal::zero(),
+                    reward_indices: holder_indices(Decimal::one(), Decimal::zero()),
+                    token_indices: vec![],
+                    withdraw_address: None,
                 },
                 HolderResponse {
                     address: String::from("addr0002"),
                     balance: Uint128::from(300u128),
-                    index: Decimal::one(),
-                    pending_rewards: Decimal::zero(),
+                    reward_indices: holder_indices(Decimal::one(), Decimal::zero()),
+                    token_indices: vec![],
+                    withdraw_address: None,
                 },
             ],
         }
@@ -561,8 +667,9 @@ fn query_holders() {
             holders: vec![HolderResponse {
                 address: String::from("addr0000"),
                 balance: Uint128::from(100u128),
-                index: Decimal::zero(),
-                pending_rewards: Decimal::zero(),
+                reward_indices: holder_indices(Decimal::zero(), Decimal::zero()),
+                token_indices: vec![],
+                withdraw_address: None,
             }],
         }
     );
@@ -585,14 +692,16 @@ fn query_holders() {
                 HolderResponse {
                     address: String::from("addr0001"),
                     balance: Uint128::from(200u128),
-                    index: Decimal::one(),
-                    pending_rewards: Decimal::zero(),
+                    reward_indices: holder_indices(Decimal::one(), Decimal::zero()),
+                    token_indices: vec![],
+                    withdraw_address: None,
                 },
                 HolderResponse {
                     address: String::from("addr0002"),
                     balance: Uint128::from(300u128),
-                    index: Decimal::one(),
-                    pending_rewards: Decimal::zero(),
+                    reward_indices: holder_indices(Decimal::one(), Decimal::zero()),
+                    token_indices: vec![],
+                    withdraw_address: None,
                 }
             ],
         }
@@ -615,9 +724,1481 @@ fn query_holders() {
             holders: vec![HolderResponse {
                 address: String::from("addr0001"),
                 balance: Uint128::from(200u128),
-                index: Decimal::one(),
-                pending_rewards: Decimal::zero(),
+                reward_indices: holder_indices(Decimal::one(), Decimal::zero()),
+                token_indices: vec![],
+                withdraw_address: None,
             }],
         }
     );
 }
+
+#[test]
+fn proper_ownership_transfer() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let new_owner = "new_owner0000";
+
+    // only the owner can propose a new owner
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: Some(u64::MAX),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("ownership proposal expiry is out of range")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("no ownership transfer is pending")
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: Some(100),
+        },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pending_owner,
+        PendingOwnerResponse {
+            pending_owner: Some(new_owner.to_string()),
+            expires_at: Some(mock_env().block.time.seconds() + 100),
+        }
+    );
+
+    // only the proposed owner can accept
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("invalid", &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    // the proposal expires
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(100);
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info(new_owner, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("ownership proposal has expired"));
+
+    // the owner can cancel the proposal
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        ExecuteMsg::CancelOwnershipTransfer {},
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::CancelOwnershipTransfer {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        pending_owner,
+        PendingOwnerResponse {
+            pending_owner: None,
+            expires_at: None,
+        }
+    );
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: new_owner.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(new_owner, &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.owner, new_owner.to_string());
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::PendingOwner {}).unwrap();
+    let pending_owner: PendingOwnerResponse = from_binary(&res).unwrap();
+    assert_eq!(pending_owner.pending_owner, None);
+
+    // the previous owner lost its rights
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: MOCK_OWNER_ADDR.to_string(),
+            expires_in: None,
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("unauthorized"));
+}
+
+#[test]
+fn proper_timelock() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::SetTimelockDelay { delay: 1000 },
+    )
+    .unwrap();
+
+    let post_initialize = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        post_initialize.clone(),
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("owner operations must be queued through the timelock")
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::ProposeAction {
+            msg: Box::new(ExecuteMsg::ClaimRewards { recipient: None }),
+        },
+    )
+    .unwrap_err();
+    assert_eq!(err, StdError::generic_err("operation cannot be queued"));
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::ProposeAction {
+            msg: Box::new(post_initialize.clone()),
+        },
+    )
+    .unwrap();
+    let eta = mock_env().block.time.seconds() + 1000;
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::QueuedAction { id: 0 }).unwrap();
+    let queued: QueuedActionResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        queued,
+        QueuedActionResponse {
+            id: 0,
+            msg: post_initialize.clone(),
+            eta,
+        }
+    );
+
+    let err = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteAction { id: 0 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err(format!("queued action is timelocked until {}", eta))
+    );
+
+    // anyone executes the action once the delay has passed
+    let mut env = mock_env();
+    env.block.time = env.block.time.plus_seconds(1000);
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteAction { id: 0 },
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config.token_contract,
+        Some(MOCK_TOKEN_CONTRACT_ADDR.to_string())
+    );
+
+    // the owner cancels a queued action
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::ProposeAction {
+            msg: Box::new(ExecuteMsg::SetTimelockDelay { delay: 0 }),
+        },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::CancelAction { id: 1 },
+    )
+    .unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedActions {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedActionsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        queued,
+        QueuedActionsResponse {
+            delay: 1000,
+            actions: vec![],
+        }
+    );
+
+    // actions queued by a previous owner do not run once ownership changes hands
+    for msg in [
+        ExecuteMsg::SetTimelockDelay { delay: 0 },
+        ExecuteMsg::ProposeNewOwner {
+            new_owner: "new_owner".to_string(),
+            expires_in: None,
+        },
+    ] {
+        execute(
+            deps.as_mut(),
+            mock_env(),
+            owner_info.clone(),
+            ExecuteMsg::ProposeAction { msg: Box::new(msg) },
+        )
+        .unwrap();
+    }
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteAction { id: 3 },
+    )
+    .unwrap();
+    execute(
+        deps.as_mut(),
+        env.clone(),
+        mock_info("new_owner", &[]),
+        ExecuteMsg::AcceptOwnership {},
+    )
+    .unwrap();
+
+    let err = execute(
+        deps.as_mut(),
+        env,
+        mock_info("addr0000", &[]),
+        ExecuteMsg::ExecuteAction { id: 2 },
+    )
+    .unwrap_err();
+    assert_eq!(
+        err,
+        StdError::generic_err("queued action was proposed by a previous owner")
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::QueuedActions {
+            start_after: Some(u64::MAX),
+            limit: None,
+        },
+    )
+    .unwrap();
+    let queued: QueuedActionsResponse = from_binary(&res).unwrap();
+    assert_eq!(queued.actions, vec![]);
+}
+
+#[test]
+fn proper_multi_denom_rewards() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(1000u128),
+        },
+    ]);
+
+    // the same denom cannot be distributed twice
+    let init_msg = InstantiateMsg {
+        owner: MOCK_OWNER_ADDR.to_string(),
+        reward_denoms: vec!["uusd".to_string(), "uusd".to_string()],
+    };
+    let res = instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "reward denom uusd is already distributed")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let init_msg = InstantiateMsg {
+        owner: MOCK_OWNER_ADDR.to_string(),
+        reward_denoms: vec!["uusd".to_string(), "ukrw".to_string()],
+    };
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::from(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    deposit_rewards(
+        &mut deps,
+        &[
+            Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(100u128),
+            },
+            Coin {
+                denom: "ukrw".to_string(),
+                amount: Uint128::new(1000u128),
+            },
+        ],
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AccruedRewards {
+            address: "addr0000".to_string(),
+        },
+    )
+    .unwrap();
+    let accrued: AccruedRewardsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        accrued,
+        AccruedRewardsResponse {
+            rewards: vec![
+                Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(100u128),
+                },
+                Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::new(1000u128),
+                },
+            ],
+        }
+    );
+
+    // only the owner can add a reward denom, once
+    let msg = ExecuteMsg::AddRewardDenom {
+        denom: "ueur".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), owner_info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "reward denom ueur is already distributed")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        config.reward_denoms,
+        vec!["uusd".to_string(), "ukrw".to_string(), "ueur".to_string()]
+    );
+
+    // every denom with rewards is paid in a single transfer
+    let msg = ExecuteMsg::ClaimRewards { recipient: None };
+    let info = mock_info("addr0000", &[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0000".to_string(),
+            amount: vec![
+                Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::from(99u128), // 1% tax
+                },
+                Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::from(990u128), // 1% tax
+                },
+            ]
+        }))]
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state.reward_indices,
+        vec![
+            reward_index(Decimal::one(), Uint128::zero()),
+            RewardIndexResponse {
+                denom: "ukrw".to_string(),
+                global_index: Decimal::from_str("10").unwrap(),
+                prev_reward_balance: Uint128::zero(),
+            },
+            RewardIndexResponse {
+                denom: "ueur".to_string(),
+                global_index: Decimal::zero(),
+                prev_reward_balance: Uint128::zero(),
+            },
+        ]
+    );
+}
+
+#[test]
+fn proper_token_rewards() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    let deposit = |amount: u128| {
+        ExecuteMsg::Receive(Cw20ReceiveMsg {
+            sender: "partner0000".to_string(),
+            amount: Uint128::new(amount),
+            msg: to_binary(&Cw20HookMsg::DepositRewards {}).unwrap(),
+        })
+    };
+
+    // only registered tokens are accepted
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_REWARD_TOKEN_ADDR, &[]),
+        deposit(1000),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "reward_token0000 is not a reward token")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let msg = ExecuteMsg::AddRewardToken {
+        token_address: MOCK_REWARD_TOKEN_ADDR.to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg.clone()).unwrap();
+    let res = execute(deps.as_mut(), mock_env(), owner_info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "reward token reward_token0000 is already distributed")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    // rewards cannot be deposited while there are no holders
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_REWARD_TOKEN_ADDR, &[]),
+        deposit(1000),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "No balance to distribute rewards to")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    for (address, amount) in [("addr0000", 100u128), ("addr0001", 300u128)] {
+        let msg = ExecuteMsg::IncreaseBalance {
+            address: address.to_string(),
+            amount: Uint128::new(amount),
+        };
+        execute(deps.as_mut(), mock_env(), info.clone(), msg).unwrap();
+    }
+
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_REWARD_TOKEN_ADDR, &[]),
+        deposit(1000),
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::RewardTokens {}).unwrap();
+    let reward_tokens: RewardTokensResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        reward_tokens,
+        RewardTokensResponse {
+            reward_tokens: vec![RewardTokenResponse {
+                token_address: MOCK_REWARD_TOKEN_ADDR.to_string(),
+                global_index: Decimal::from_str("2.5").unwrap(),
+            }],
+        }
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AccruedTokenRewards {
+            address: "addr0000".to_string(),
+        },
+    )
+    .unwrap();
+    let accrued: AccruedTokenRewardsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        accrued,
+        AccruedTokenRewardsResponse {
+            rewards: vec![Cw20Coin {
+                address: MOCK_REWARD_TOKEN_ADDR.to_string(),
+                amount: Uint128::new(250),
+            }],
+        }
+    );
+
+    // cw20 rewards are paid with a transfer, without any native rewards
+    let msg = ExecuteMsg::ClaimRewards { recipient: None };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0001", &[]),
+        msg.clone(),
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(WasmMsg::Execute {
+            contract_addr: MOCK_REWARD_TOKEN_ADDR.to_string(),
+            msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                recipient: "addr0001".to_string(),
+                amount: Uint128::new(750),
+            })
+            .unwrap(),
+            funds: vec![],
+        })]
+    );
+
+    let res = execute(deps.as_mut(), mock_env(), mock_info("addr0001", &[]), msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No rewards have accrued yet"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+}
+
+#[test]
+fn proper_deposit_and_recover() {
+    let mut deps = mock_dependencies(&[
+        Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(150u128),
+        },
+        Coin {
+            denom: "ukrw".to_string(),
+            amount: Uint128::new(20u128),
+        },
+    ]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    let uusd = Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let deposit = |funds: &[Coin]| {
+        (
+            mock_info("depositor0000", funds),
+            ExecuteMsg::DepositRewards {},
+        )
+    };
+
+    let (info, msg) = deposit(&[]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No rewards sent"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    // rewards cannot be deposited while there are no holders
+    let (info, msg) = deposit(std::slice::from_ref(&uusd));
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => {
+            assert_eq!(msg, "No balance to distribute rewards to")
+        }
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let (info, msg) = deposit(&[Coin {
+        denom: "ukrw".to_string(),
+        amount: Uint128::new(20u128),
+    }]);
+    let res = execute(deps.as_mut(), mock_env(), info, msg);
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "ukrw is not a reward denom"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let (info, msg) = deposit(&[uusd]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    // coins sent without a deposit are not distributed
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::AccruedRewards {
+            address: "addr0000".to_string(),
+        },
+    )
+    .unwrap();
+    let accrued: AccruedRewardsResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        accrued.rewards,
+        vec![Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(100u128),
+        }]
+    );
+
+    let msg = ExecuteMsg::RecoverUnaccounted {
+        recipient: "treasury0000".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    let res = execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0000".to_string(),
+            amount: vec![
+                Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(49u128), // 1% tax
+                },
+                Coin {
+                    denom: "ukrw".to_string(),
+                    amount: Uint128::new(19u128), // 1% tax
+                },
+            ]
+        }))]
+    );
+}
+
+// storage of an instance deployed with a single reward denom
+fn store_legacy_layout(
+    deps: &mut OwnedDeps<MockStorage, MockApi, WasmMockQuerier>,
+    state: LegacyState,
+    holders: &[(&str, u128, Decimal, Decimal)],
+) {
+    let owner_raw = deps.api.addr_canonicalize(MOCK_OWNER_ADDR).unwrap();
+    let token_raw = deps
+        .api
+        .addr_canonicalize(MOCK_TOKEN_CONTRACT_ADDR)
+        .unwrap();
+    singleton(&mut deps.storage, KEY_CONFIG)
+        .save(&LegacyConfig {
+            owner: owner_raw,
+            token_contract: Some(token_raw),
+            reward_denom: "uusd".to_string(),
+        })
+        .unwrap();
+    singleton(&mut deps.storage, KEY_STATE)
+        .save(&state)
+        .unwrap();
+    for (address, balance, index, pending_rewards) in holders.iter() {
+        let address_raw = deps.api.addr_canonicalize(address).unwrap();
+        bucket(&mut deps.storage, PREFIX_HOLDERS)
+            .save(
+                address_raw.as_slice(),
+                &LegacyHolder {
+                    balance: Uint128::new(*balance),
+                    index: *index,
+                    pending_rewards: *pending_rewards,
+                },
+            )
+            .unwrap();
+    }
+}
+
+#[test]
+fn proper_migrate_legacy_layout() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100u128),
+    }]);
+
+    store_legacy_layout(
+        &mut deps,
+        LegacyState {
+            global_index: Decimal::one(),
+            total_balance: Uint128::new(300u128),
+            prev_reward_balance: Uint128::new(100u128),
+        },
+        &[
+            ("addr0000", 100u128, Decimal::percent(50), Decimal::zero()),
+            (
+                "addr0001",
+                200u128,
+                Decimal::one(),
+                Decimal::from_ratio(50u128, 1u128),
+            ),
+        ],
+    );
+
+    // the legacy layout cannot be read before the migration
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    assert!(execute(deps.as_mut(), mock_env(), info, msg).is_err());
+
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::Config {}).unwrap();
+    let config: ConfigResponse = from_binary(&res).unwrap();
+    assert_eq!(config.reward_denoms, vec!["uusd".to_string()]);
+    assert_eq!(
+        config.token_contract,
+        Some(MOCK_TOKEN_CONTRACT_ADDR.to_string())
+    );
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state,
+        StateResponse {
+            total_balance: Uint128::new(300u128),
+            reward_indices: vec![reward_index(Decimal::one(), Uint128::new(100u128))],
+        }
+    );
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Holder {
+            address: "addr0001".to_string(),
+        },
+    )
+    .unwrap();
+    let holder: HolderResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        holder.reward_indices,
+        holder_indices(Decimal::one(), Decimal::from_ratio(50u128, 1u128))
+    );
+
+    // both holders claim what they were owed before the migration
+    for address in ["addr0000", "addr0001"] {
+        let msg = ExecuteMsg::ClaimRewards { recipient: None };
+        let res = execute(deps.as_mut(), mock_env(), mock_info(address, &[]), msg).unwrap();
+        assert_eq!(
+            res.messages,
+            vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+                to_address: address.to_string(),
+                amount: vec![Coin {
+                    denom: "uusd".to_string(),
+                    amount: Uint128::new(49u128), // 1% tax
+                }]
+            }))]
+        );
+    }
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state.reward_indices,
+        vec![reward_index(Decimal::one(), Uint128::zero())]
+    );
+}
+
+#[test]
+fn proper_migrate_legacy_holders_lazily() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100u128),
+    }]);
+    store_legacy_layout(
+        &mut deps,
+        LegacyState {
+            global_index: Decimal::one(),
+            total_balance: Uint128::new(300u128),
+            prev_reward_balance: Uint128::new(100u128),
+        },
+        &[
+            ("addr0000", 100u128, Decimal::percent(50), Decimal::zero()),
+            ("addr0001", 200u128, Decimal::one(), Decimal::zero()),
+        ],
+    );
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+
+    // the holders are left in the legacy layout by the migration
+    let address_raw = deps.api.addr_canonicalize("addr0000").unwrap();
+    let legacy_holder: LegacyHolder = bucket_read(&deps.storage, PREFIX_HOLDERS)
+        .load(address_raw.as_slice())
+        .unwrap();
+    assert_eq!(legacy_holder.balance, Uint128::new(100u128));
+
+    // and are read in the current one
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Holders {
+            start_after: None,
+            limit: None,
+        },
+    )
+    .unwrap();
+    let holders_response: HoldersResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        holders_response.holders[0],
+        HolderResponse {
+            address: "addr0000".to_string(),
+            balance: Uint128::new(100u128),
+            reward_indices: holder_indices(Decimal::percent(50), Decimal::zero()),
+            token_indices: vec![],
+            withdraw_address: None,
+        }
+    );
+    assert_eq!(holders_response.holders.len(), 2);
+
+    // the holder is stored in the current layout once updated
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let holder: Holder = bucket_read(&deps.storage, PREFIX_HOLDERS)
+        .load(address_raw.as_slice())
+        .unwrap();
+    assert_eq!(
+        holder,
+        Holder {
+            balance: Uint128::new(200u128),
+            reward_indices: vec![HolderIndex {
+                denom: DEFAULT_REWARD_DENOM.to_string(),
+                index: Decimal::one(),
+                pending_rewards: Decimal::from_ratio(50u128, 1u128),
+            }],
+            token_indices: vec![],
+            withdraw_address: None,
+        }
+    );
+}
+
+#[test]
+fn proper_migrate_balance_diff() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(150u128),
+    }]);
+    // 50uusd were received since the last update of the global index
+    store_legacy_layout(
+        &mut deps,
+        LegacyState {
+            global_index: Decimal::one(),
+            total_balance: Uint128::new(100u128),
+            prev_reward_balance: Uint128::new(100u128),
+        },
+        &[("addr0000", 100u128, Decimal::one(), Decimal::zero())],
+    );
+
+    // the rewards received before the migration are credited once
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state.reward_indices,
+        vec![reward_index(
+            Decimal::from_str("1.5").unwrap(),
+            Uint128::new(150u128)
+        )]
+    );
+
+    // coins received afterwards stay unaccounted through later migrations
+    deps.querier._balances(&[(
+        MOCK_CONTRACT_ADDR,
+        &[Coin {
+            denom: "uusd".to_string(),
+            amount: Uint128::new(200u128),
+        }],
+    )]);
+    migrate(deps.as_mut(), mock_env(), MigrateMsg {}).unwrap();
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state.reward_indices,
+        vec![reward_index(
+            Decimal::from_str("1.5").unwrap(),
+            Uint128::new(150u128)
+        )]
+    );
+
+    let msg = ExecuteMsg::RecoverUnaccounted {
+        recipient: "treasury0000".to_string(),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "treasury0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(49u128), // 1% tax
+            }]
+        }))]
+    );
+}
+
+#[test]
+fn proper_streamed_rewards() {
+    let mut deps = mock_dependencies(&[]);
+    let init_msg = default_init();
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        init_msg,
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateDistributionPeriod {
+        distribution_period: 100,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info, msg).unwrap();
+
+    let start = mock_env().block.time.seconds();
+    let env_at = |elapsed: u64| {
+        let mut env = mock_env();
+        env.block.time = env.block.time.plus_seconds(elapsed);
+        env
+    };
+    let accrued =
+        |deps: &OwnedDeps<MockStorage, MockApi, WasmMockQuerier>, elapsed: u64, address: &str| {
+            let res = query(
+                deps.as_ref(),
+                env_at(elapsed),
+                QueryMsg::AccruedRewards {
+                    address: address.to_string(),
+                },
+            )
+            .unwrap();
+            let accrued: AccruedRewardsResponse = from_binary(&res).unwrap();
+            accrued.rewards
+        };
+    let uusd = |amount: u128| Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(amount),
+    };
+
+    let token_info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    execute(deps.as_mut(), env_at(0), token_info.clone(), msg).unwrap();
+
+    // the deposit is released over the distribution period
+    let info = mock_info("depositor0000", &[uusd(1000)]);
+    execute(
+        deps.as_mut(),
+        env_at(0),
+        info,
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap();
+    assert_eq!(accrued(&deps, 0, "addr0000"), vec![]);
+    assert_eq!(accrued(&deps, 30, "addr0000"), vec![uusd(300)]);
+
+    let res = query(deps.as_ref(), env_at(30), QueryMsg::Distribution {}).unwrap();
+    let distribution: DistributionResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        distribution,
+        DistributionResponse {
+            distribution_period: 100,
+            streams: vec![RewardStreamResponse {
+                reward: "uusd".to_string(),
+                emission_rate: Decimal::from_str("10").unwrap(),
+                undistributed: Uint128::new(700u128),
+                end: start + 100,
+            }],
+        }
+    );
+
+    // a holder joining later only gets what is released afterwards
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0001".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    execute(deps.as_mut(), env_at(30), token_info, msg).unwrap();
+
+    // a new deposit is streamed over its own period, next to what is left
+    let info = mock_info("depositor0000", &[uusd(300)]);
+    execute(
+        deps.as_mut(),
+        env_at(30),
+        info,
+        ExecuteMsg::DepositRewards {},
+    )
+    .unwrap();
+    let res = query(deps.as_ref(), env_at(30), QueryMsg::Distribution {}).unwrap();
+    let distribution: DistributionResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        distribution.streams,
+        vec![RewardStreamResponse {
+            reward: "uusd".to_string(),
+            emission_rate: Decimal::from_str("13").unwrap(),
+            undistributed: Uint128::new(1000u128),
+            end: start + 130,
+        }]
+    );
+
+    // the first deposit is still fully released at its original end
+    let res = query(deps.as_ref(), env_at(100), QueryMsg::Distribution {}).unwrap();
+    let distribution: DistributionResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        distribution.streams,
+        vec![RewardStreamResponse {
+            reward: "uusd".to_string(),
+            emission_rate: Decimal::from_str("3").unwrap(),
+            undistributed: Uint128::new(90u128),
+            end: start + 130,
+        }]
+    );
+    assert_eq!(accrued(&deps, 100, "addr0000"), vec![uusd(755)]);
+
+    assert_eq!(accrued(&deps, 130, "addr0000"), vec![uusd(800)]);
+    assert_eq!(accrued(&deps, 200, "addr0001"), vec![uusd(500)]);
+
+    let msg = ExecuteMsg::ClaimRewards { recipient: None };
+    let res = execute(deps.as_mut(), env_at(200), mock_info("addr0001", &[]), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: vec![uusd(495)], // 1% tax
+        }))]
+    );
+
+    let res = query(deps.as_ref(), env_at(200), QueryMsg::Distribution {}).unwrap();
+    let distribution: DistributionResponse = from_binary(&res).unwrap();
+    assert_eq!(distribution.streams[0].undistributed, Uint128::zero());
+    assert_eq!(distribution.streams[0].emission_rate, Decimal::zero());
+}
+
+#[test]
+fn proper_swap_to_reward_denom() {
+    let coin = |denom: &str, amount: u128| Coin {
+        denom: denom.to_string(),
+        amount: Uint128::new(amount),
+    };
+    let mut deps = mock_dependencies(&[
+        coin("uusd", 100),
+        coin("uluna", 50000),
+        coin("ukrw", 20000),
+        coin("umnt", 5000),
+    ]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        default_init(),
+    )
+    .unwrap();
+
+    let owner_info = mock_info(MOCK_OWNER_ADDR, &[]);
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let msg = ExecuteMsg::UpdateSwapConfig {
+        skip_denoms: Some(vec!["ukrw".to_string()]),
+        dust_threshold: None,
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+    execute(deps.as_mut(), mock_env(), owner_info.clone(), msg).unwrap();
+
+    // anyone can swap, skipped denoms and dust are left alone
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone0000", &[]),
+        ExecuteMsg::SwapToRewardDenom {},
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![
+            SubMsg::new(create_swap_msg(coin("uluna", 50000), "uusd".to_string())),
+            SubMsg::new(WasmMsg::Execute {
+                contract_addr: MOCK_CONTRACT_ADDR.to_string(),
+                msg: to_binary(&ExecuteMsg::UpdateGlobalIndex {
+                    prev_reward_balance: Uint128::new(100u128),
+                })
+                .unwrap(),
+                funds: vec![],
+            }),
+        ]
+    );
+
+    // the callback can only be sent by the contract itself
+    let msg = ExecuteMsg::UpdateGlobalIndex {
+        prev_reward_balance: Uint128::new(100u128),
+    };
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone0000", &[]),
+        msg.clone(),
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "unauthorized"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+
+    // the swap returned 300uusd, on top of the 100uusd left unaccounted
+    deps.querier._balances(&[(
+        MOCK_CONTRACT_ADDR,
+        &[coin("uusd", 400), coin("ukrw", 20000), coin("umnt", 5000)],
+    )]);
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_CONTRACT_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+
+    let res = query(deps.as_ref(), mock_env(), QueryMsg::State {}).unwrap();
+    let state_response: StateResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        state_response.reward_indices,
+        vec![reward_index(
+            Decimal::from_ratio(3u128, 1u128),
+            Uint128::new(300u128)
+        )]
+    );
+
+    // the skipped denoms and the 100uusd held before the swap can still be recovered
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        owner_info.clone(),
+        ExecuteMsg::RecoverUnaccounted {
+            recipient: MOCK_OWNER_ADDR.to_string(),
+        },
+    )
+    .unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(BankMsg::Send {
+            to_address: MOCK_OWNER_ADDR.to_string(),
+            amount: vec![coin("uusd", 99), coin("ukrw", 19801), coin("umnt", 4950)],
+        })]
+    );
+
+    let res = execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("anyone0000", &[]),
+        ExecuteMsg::SwapToRewardDenom {},
+    );
+    match res {
+        Err(StdError::GenericErr { msg, .. }) => assert_eq!(msg, "No balance to swap"),
+        _ => panic!("DO NOT ENTER HERE"),
+    };
+}
+
+#[test]
+fn proper_withdraw_address() {
+    let mut deps = mock_dependencies(&[Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(200u128),
+    }]);
+    instantiate(
+        deps.as_mut(),
+        mock_env(),
+        mock_info("addr0000", &[]),
+        default_init(),
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::PostInitialize {
+        token_contract: MOCK_TOKEN_CONTRACT_ADDR.to_string(),
+    };
+    execute(
+        deps.as_mut(),
+        mock_env(),
+        mock_info(MOCK_OWNER_ADDR, &[]),
+        msg,
+    )
+    .unwrap();
+
+    let msg = ExecuteMsg::IncreaseBalance {
+        address: "addr0000".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    let info = mock_info(MOCK_TOKEN_CONTRACT_ADDR, &[]);
+    execute(deps.as_mut(), mock_env(), info, msg).unwrap();
+
+    let uusd = Coin {
+        denom: "uusd".to_string(),
+        amount: Uint128::new(100u128),
+    };
+    deposit_rewards(&mut deps, std::slice::from_ref(&uusd));
+
+    let holder_info = mock_info("addr0000", &[]);
+    let msg = ExecuteMsg::SetWithdrawAddress {
+        address: "vault0000".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), holder_info.clone(), msg).unwrap();
+
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Holder {
+            address: "addr0000".to_string(),
+        },
+    )
+    .unwrap();
+    let holder_response: HolderResponse = from_binary(&res).unwrap();
+    assert_eq!(
+        holder_response.withdraw_address,
+        Some("vault0000".to_string())
+    );
+
+    // rewards claimed without a recipient go to the withdraw address
+    let msg = ExecuteMsg::ClaimRewards { recipient: None };
+    let res = execute(deps.as_mut(), mock_env(), holder_info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "vault0000".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(99u128), // 1% tax
+            }]
+        }))]
+    );
+
+    // an explicit recipient takes precedence
+    deposit_rewards(&mut deps, std::slice::from_ref(&uusd));
+    let msg = ExecuteMsg::ClaimRewards {
+        recipient: Some("addr0001".to_string()),
+    };
+    let res = execute(deps.as_mut(), mock_env(), holder_info.clone(), msg).unwrap();
+    assert_eq!(
+        res.messages,
+        vec![SubMsg::new(CosmosMsg::Bank(BankMsg::Send {
+            to_address: "addr0001".to_string(),
+            amount: vec![Coin {
+                denom: "uusd".to_string(),
+                amount: Uint128::new(99u128), // 1% tax
+            }]
+        }))]
+    );
+
+    // setting the holder's own address restores the default
+    let msg = ExecuteMsg::SetWithdrawAddress {
+        address: "addr0000".to_string(),
+    };
+    execute(deps.as_mut(), mock_env(), holder_info, msg).unwrap();
+    let res = query(
+        deps.as_ref(),
+        mock_env(),
+        QueryMsg::Holder {
+            address: "addr0000".to_string(),
+        },
+    )
+    .unwrap();
+    let holder_response: HolderResponse = from_binary(&res).unwrap();
+    assert_eq!(holder_response.withdraw_address, None);
+}
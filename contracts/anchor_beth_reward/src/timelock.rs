@@ -0,0 +1,182 @@
+use crate::contract::dispatch;
+use crate::state::{
+    read_config, read_queued_action, read_queued_actions, read_timelock, remove_queued_action,
+    store_queued_action, store_timelock, QueuedAction,
+};
+
+use beth::reward::{ExecuteMsg, QueuedActionResponse, QueuedActionsResponse};
+use cosmwasm_std::{attr, Deps, DepsMut, Env, MessageInfo, Response, StdError, StdResult};
+use terra_cosmwasm::TerraMsgWrapper;
+
+// the delay cannot lock the owner out for longer than 30 days
+pub const MAX_TIMELOCK_DELAY: u64 = 30 * 24 * 60 * 60;
+
+/// Owner operations that have to be queued once a timelock delay is set
+pub(crate) fn is_timelocked(msg: &ExecuteMsg) -> bool {
+    matches!(
+        msg,
+        ExecuteMsg::PostInitialize { .. }
+            | ExecuteMsg::AddRewardDenom { .. }
+            | ExecuteMsg::AddRewardToken { .. }
+            | ExecuteMsg::RecoverUnaccounted { .. }
+            | ExecuteMsg::UpdateDistributionPeriod { .. }
+            | ExecuteMsg::UpdateSwapConfig { .. }
+            | ExecuteMsg::ProposeNewOwner { .. }
+            | ExecuteMsg::SetTimelockDelay { .. }
+    )
+}
+
+pub fn execute_propose_action(
+    deps: DepsMut,
+    env: Env,
+    info: MessageInfo,
+    msg: ExecuteMsg,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if !is_timelocked(&msg) {
+        return Err(StdError::generic_err("operation cannot be queued"));
+    }
+
+    let mut timelock = read_timelock(deps.storage)?;
+    let id = timelock.next_action_id;
+    let eta = env
+        .block
+        .time
+        .seconds()
+        .checked_add(timelock.delay)
+        .ok_or_else(|| StdError::generic_err("queued action eta is out of range"))?;
+    store_queued_action(
+        deps.storage,
+        id,
+        &QueuedAction {
+            msg,
+            eta,
+            proposer: config.owner,
+        },
+    )?;
+    timelock.next_action_id += 1;
+    store_timelock(deps.storage, &timelock)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "propose_action"),
+        attr("id", id.to_string()),
+        attr("eta", eta.to_string()),
+    ]))
+}
+
+pub fn execute_cancel_action(
+    deps: DepsMut,
+    info: MessageInfo,
+    id: u64,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if read_queued_action(deps.storage, id)?.is_none() {
+        return Err(StdError::generic_err(format!(
+            "no queued action with id {}",
+            id
+        )));
+    }
+    remove_queued_action(deps.storage, id);
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "cancel_action"),
+        attr("id", id.to_string()),
+    ]))
+}
+
+/// Run a queued operation on behalf of the owner that proposed it
+pub fn execute_queued_action(
+    deps: DepsMut,
+    env: Env,
+    id: u64,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let action = read_queued_action(deps.storage, id)?
+        .ok_or_else(|| StdError::generic_err(format!("no queued action with id {}", id)))?;
+    if env.block.time.seconds() < action.eta {
+        return Err(StdError::generic_err(format!(
+            "queued action is timelocked until {}",
+            action.eta
+        )));
+    }
+
+    // actions queued by a previous owner must not run with the new owner's authority
+    let config = read_config(deps.storage)?;
+    if action.proposer != config.owner {
+        return Err(StdError::generic_err(
+            "queued action was proposed by a previous owner",
+        ));
+    }
+    remove_queued_action(deps.storage, id);
+
+    let owner = deps.api.addr_humanize(&config.owner)?;
+    let owner_info = MessageInfo {
+        sender: owner,
+        funds: vec![],
+    };
+    let res = dispatch(deps, env, owner_info, action.msg)?;
+
+    Ok(res.add_attribute("queued_action_id", id.to_string()))
+}
+
+pub fn execute_set_timelock_delay(
+    deps: DepsMut,
+    info: MessageInfo,
+    delay: u64,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let config = read_config(deps.storage)?;
+    let owner_addr = deps.api.addr_humanize(&config.owner)?;
+
+    if info.sender != owner_addr {
+        return Err(StdError::generic_err("unauthorized"));
+    }
+
+    if delay > MAX_TIMELOCK_DELAY {
+        return Err(StdError::generic_err(format!(
+            "timelock delay cannot exceed {} seconds",
+            MAX_TIMELOCK_DELAY
+        )));
+    }
+
+    let mut timelock = read_timelock(deps.storage)?;
+    timelock.delay = delay;
+    store_timelock(deps.storage, &timelock)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_timelock_delay"),
+        attr("delay", delay.to_string()),
+    ]))
+}
+
+pub fn query_queued_action(deps: Deps, id: u64) -> StdResult<QueuedActionResponse> {
+    let action = read_queued_action(deps.storage, id)?
+        .ok_or_else(|| StdError::generic_err(format!("no queued action with id {}", id)))?;
+
+    Ok(QueuedActionResponse {
+        id,
+        msg: action.msg,
+        eta: action.eta,
+    })
+}
+
+pub fn query_queued_actions(
+    deps: Deps,
+    start_after: Option<u64>,
+    limit: Option<u32>,
+) -> StdResult<QueuedActionsResponse> {
+    Ok(QueuedActionsResponse {
+        delay: read_timelock(deps.storage)?.delay,
+        actions: read_queued_actions(deps.storage, start_after, limit)?,
+    })
+}
@@ -1,17 +1,19 @@
 use crate::state::{
-    read_config, read_contract_addr, read_holder, read_holders, read_state, store_holder,
-    store_state, Config, Holder, State,
+    read_config, read_holder, read_holders, read_state, store_holder, store_state, Holder,
+    HolderIndex, State,
 };
 use beth::reward::{AccruedRewardsResponse, HolderResponse, HoldersResponse};
 
 use cosmwasm_std::{
-    attr, Addr, BankMsg, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env, MessageInfo,
-    Response, StdError, StdResult, Uint128,
+    attr, to_binary, Addr, BankMsg, CanonicalAddr, Coin, CosmosMsg, Decimal, Deps, DepsMut, Env,
+    MessageInfo, Response, StdError, StdResult, Uint128, WasmMsg,
 };
+use cw20::{Cw20Coin, Cw20ExecuteMsg};
 
 use crate::math::{
     decimal_multiplication_in_256, decimal_subtraction_in_256, decimal_summation_in_256,
 };
+use crate::stream::release_rewards;
 use beth::deduct_tax;
 use std::str::FromStr;
 use terra_cosmwasm::TerraMsgWrapper;
@@ -22,66 +24,128 @@ pub fn execute_claim_rewards(
     info: MessageInfo,
     recipient: Option<Addr>,
 ) -> StdResult<Response<TerraMsgWrapper>> {
-    let contract_addr = env.contract.address;
     let holder_addr = info.sender.clone();
     let holder_addr_raw = deps.api.addr_canonicalize(holder_addr.as_str())?;
-    let recipient = match recipient {
-        Some(value) => value,
-        None => info.sender,
-    };
-
     let mut holder: Holder = read_holder(deps.storage, &holder_addr_raw)?;
+
+    // an explicit recipient takes precedence over the withdraw address
+    let recipient = match (recipient, &holder.withdraw_address) {
+        (Some(value), _) => value,
+        (None, Some(withdraw_address)) => deps.api.addr_humanize(withdraw_address)?,
+        (None, None) => info.sender,
+    };
     let mut state: State = read_state(deps.storage)?;
-    let config: Config = read_config(deps.storage)?;
 
-    // Load the reward contract balance
-    let reward_balance = deps
-        .querier
-        .query_balance(contract_addr, config.reward_denom.as_str())
-        .unwrap();
+    release_rewards(&mut state, env.block.time.seconds());
+    accrue_holder_rewards(&state, &mut holder)?;
 
-    // Update state's global index before calculating user rewards
-    update_global_index(&mut state, reward_balance.amount)?;
+    let mut rewards: Vec<Coin> = vec![];
+    for reward_index in state.reward_indices.iter_mut() {
+        let holder_index = holder.reward_index(&reward_index.denom);
+        let amount = holder_index.pending_rewards * Uint128::new(1);
+        if amount.is_zero() {
+            continue;
+        }
 
-    let reward_with_decimals =
-        calculate_decimal_rewards(state.global_index, holder.index, holder.balance)?;
+        // keep the fraction which cannot be paid out yet
+        holder_index.pending_rewards = get_decimals(holder_index.pending_rewards)?;
+        reward_index.prev_reward_balance = reward_index.prev_reward_balance.checked_sub(amount)?;
+        rewards.push(Coin {
+            denom: reward_index.denom.clone(),
+            amount,
+        });
+    }
 
-    let all_reward_with_decimals =
-        decimal_summation_in_256(reward_with_decimals, holder.pending_rewards);
-    let decimals = get_decimals(all_reward_with_decimals).unwrap();
+    let mut token_rewards: Vec<Cw20Coin> = vec![];
+    for token_index in state.token_indices.iter() {
+        let holder_index = holder.token_index(&token_index.token);
+        let amount = holder_index.pending_rewards * Uint128::new(1);
+        if amount.is_zero() {
+            continue;
+        }
 
-    let rewards = all_reward_with_decimals * Uint128::new(1);
+        holder_index.pending_rewards = get_decimals(holder_index.pending_rewards)?;
+        token_rewards.push(Cw20Coin {
+            address: deps.api.addr_humanize(&token_index.token)?.to_string(),
+            amount,
+        });
+    }
 
-    if rewards.is_zero() {
+    if rewards.is_empty() && token_rewards.is_empty() {
         return Err(StdError::generic_err("No rewards have accrued yet"));
     }
 
-    state.prev_reward_balance = state.prev_reward_balance.checked_sub(rewards)?;
     store_state(deps.storage, &state)?;
-
-    holder.pending_rewards = decimals;
-    holder.index = state.global_index;
     store_holder(deps.storage, &holder_addr_raw, &holder)?;
 
-    let bank_msg: CosmosMsg<TerraMsgWrapper> = BankMsg::Send {
-        to_address: recipient.to_string(),
-        amount: vec![deduct_tax(
-            deps.as_ref(),
-            Coin {
-                denom: config.reward_denom,
-                amount: rewards,
-            },
-        )?],
+    let rewards_attr = rewards
+        .iter()
+        .map(|coin| coin.to_string())
+        .chain(
+            token_rewards
+                .iter()
+                .map(|coin| format!("{}{}", coin.amount, coin.address)),
+        )
+        .collect::<Vec<_>>()
+        .join(",");
+
+    let mut messages: Vec<CosmosMsg<TerraMsgWrapper>> = vec![];
+    if !rewards.is_empty() {
+        messages.push(
+            BankMsg::Send {
+                to_address: recipient.to_string(),
+                amount: rewards
+                    .into_iter()
+                    .map(|coin| deduct_tax(deps.as_ref(), coin))
+                    .collect::<StdResult<Vec<_>>>()?,
+            }
+            .into(),
+        );
+    }
+    for coin in token_rewards {
+        messages.push(
+            WasmMsg::Execute {
+                contract_addr: coin.address,
+                msg: to_binary(&Cw20ExecuteMsg::Transfer {
+                    recipient: recipient.to_string(),
+                    amount: coin.amount,
+                })?,
+                funds: vec![],
+            }
+            .into(),
+        );
     }
-    .into();
 
     Ok(Response::new()
         .add_attributes(vec![
             attr("action", "claim_reward"),
             attr("holder_address", holder_addr),
-            attr("rewards", rewards),
+            attr("rewards", rewards_attr),
         ])
-        .add_message(bank_msg))
+        .add_messages(messages))
+}
+
+pub fn execute_set_withdraw_address(
+    deps: DepsMut,
+    info: MessageInfo,
+    address: Addr,
+) -> StdResult<Response<TerraMsgWrapper>> {
+    let holder_addr_raw = deps.api.addr_canonicalize(info.sender.as_str())?;
+    let mut holder: Holder = read_holder(deps.storage, &holder_addr_raw)?;
+
+    // setting the holder's own address restores the default
+    holder.withdraw_address = if address == info.sender {
+        None
+    } else {
+        Some(deps.api.addr_canonicalize(address.as_str())?)
+    };
+    store_holder(deps.storage, &holder_addr_raw, &holder)?;
+
+    Ok(Response::new().add_attributes(vec![
+        attr("action", "set_withdraw_address"),
+        attr("holder_address", info.sender),
+        attr("withdraw_address", address),
+    ]))
 }
 
 pub fn execute_increase_balance(
@@ -104,20 +168,10 @@ pub fn execute_increase_balance(
     let mut state: State = read_state(deps.storage)?;
     let mut holder: Holder = read_holder(deps.storage, &address_raw)?;
 
-    // Load the reward contract balance
-    let reward_balance = deps
-        .querier
-        .query_balance(env.contract.address, config.reward_denom.as_str())
-        .unwrap();
-
-    // Update state's global index
-    update_global_index(&mut state, reward_balance.amount)?;
-
-    // Get decimals
-    let rewards = calculate_decimal_rewards(state.global_index, holder.index, holder.balance)?;
+    // Release the streamed rewards and move the holder's ones to pending rewards
+    release_rewards(&mut state, env.block.time.seconds());
+    accrue_holder_rewards(&state, &mut holder)?;
 
-    holder.index = state.global_index;
-    holder.pending_rewards = decimal_summation_in_256(rewards, holder.pending_rewards);
     holder.balance += amount;
     state.total_balance += amount;
 
@@ -157,19 +211,10 @@ pub fn execute_decrease_balance(
         )));
     }
 
-    // Load the reward contract balance
-    let reward_balance = deps
-        .querier
-        .query_balance(env.contract.address, config.reward_denom.as_str())
-        .unwrap();
-
-    // Update state's global index
-    update_global_index(&mut state, reward_balance.amount)?;
-
-    let rewards = calculate_decimal_rewards(state.global_index, holder.index, holder.balance)?;
+    // Release the streamed rewards and move the holder's ones to pending rewards
+    release_rewards(&mut state, env.block.time.seconds());
+    accrue_holder_rewards(&state, &mut holder)?;
 
-    holder.index = state.global_index;
-    holder.pending_rewards = decimal_summation_in_256(rewards, holder.pending_rewards);
     holder.balance = holder.balance.checked_sub(amount).unwrap();
     state.total_balance = state.total_balance.checked_sub(amount).unwrap();
 
@@ -183,57 +228,53 @@ pub fn execute_decrease_balance(
     ]))
 }
 
-/// Increase global_index according to claimed rewards amount
-fn update_global_index(state: &mut State, reward_balance: Uint128) -> StdResult<()> {
-    // Zero staking balance check
-    if state.total_balance.is_zero() {
-        // nothing balance, skip update
-        return Ok(());
+/// Move the rewards accrued since the holder's indices to pending rewards
+/// and set the holder's indices to the global ones
+pub(crate) fn accrue_holder_rewards(state: &State, holder: &mut Holder) -> StdResult<()> {
+    let balance = holder.balance;
+    for reward_index in state.reward_indices.iter() {
+        let holder_index = holder.reward_index(&reward_index.denom);
+        let rewards =
+            calculate_decimal_rewards(reward_index.global_index, holder_index.index, balance)?;
+
+        holder_index.index = reward_index.global_index;
+        holder_index.pending_rewards =
+            decimal_summation_in_256(rewards, holder_index.pending_rewards);
     }
-
-    // No change check
-    if state.prev_reward_balance == reward_balance {
-        // balance didnt change, skip update
-        return Ok(());
+    for token_index in state.token_indices.iter() {
+        let holder_index = holder.token_index(&token_index.token);
+        let rewards =
+            calculate_decimal_rewards(token_index.global_index, holder_index.index, balance)?;
+
+        holder_index.index = token_index.global_index;
+        holder_index.pending_rewards =
+            decimal_summation_in_256(rewards, holder_index.pending_rewards);
     }
 
-    // claimed_rewards = current_balance - prev_balance;
-    let claimed_rewards = reward_balance.checked_sub(state.prev_reward_balance)?;
-
-    // update state
-    state.prev_reward_balance = reward_balance;
-    // global_index += claimed_rewards / total_balance;
-    state.global_index = decimal_summation_in_256(
-        state.global_index,
-        Decimal::from_ratio(claimed_rewards, state.total_balance),
-    );
-
     Ok(())
 }
 
-pub fn query_accrued_rewards(deps: Deps, address: Addr) -> StdResult<AccruedRewardsResponse> {
+pub fn query_accrued_rewards(
+    deps: Deps,
+    env: Env,
+    address: Addr,
+) -> StdResult<AccruedRewardsResponse> {
     let mut state = read_state(deps.storage)?;
-    let config = read_config(deps.storage)?;
-
-    let contract_addr_raw = read_contract_addr(deps.storage)?;
-    let contract_addr = deps.api.addr_humanize(&contract_addr_raw)?;
-
-    // Load the reward contract balance
-    let reward_balance = deps
-        .querier
-        .query_balance(contract_addr, config.reward_denom.as_str())
-        .unwrap();
-
-    // Update state's global index
-    update_global_index(&mut state, reward_balance.amount)?;
-
-    let holder: Holder = read_holder(deps.storage, &deps.api.addr_canonicalize(address.as_str())?)?;
-    let reward_with_decimals =
-        calculate_decimal_rewards(state.global_index, holder.index, holder.balance)?;
-    let all_reward_with_decimals =
-        decimal_summation_in_256(reward_with_decimals, holder.pending_rewards);
-
-    let rewards = all_reward_with_decimals * Uint128::new(1);
+    release_rewards(&mut state, env.block.time.seconds());
+
+    let mut holder: Holder =
+        read_holder(deps.storage, &deps.api.addr_canonicalize(address.as_str())?)?;
+    accrue_holder_rewards(&state, &mut holder)?;
+
+    let rewards = holder
+        .reward_indices
+        .into_iter()
+        .map(|holder_index| Coin {
+            denom: holder_index.denom,
+            amount: holder_index.pending_rewards * Uint128::new(1),
+        })
+        .filter(|coin| !coin.amount.is_zero())
+        .collect();
 
     Ok(AccruedRewardsResponse { rewards })
 }
@@ -243,8 +284,20 @@ pub fn query_holder(deps: Deps, address: Addr) -> StdResult<HolderResponse> {
     Ok(HolderResponse {
         address: address.to_string(),
         balance: holder.balance,
-        index: holder.index,
-        pending_rewards: holder.pending_rewards,
+        reward_indices: holder
+            .reward_indices
+            .into_iter()
+            .map(HolderIndex::into_response)
+            .collect(),
+        token_indices: holder
+            .token_indices
+            .into_iter()
+            .map(|token_index| token_index.into_response(deps))
+            .collect::<StdResult<Vec<_>>>()?,
+        withdraw_address: holder
+            .withdraw_address
+            .map(|addr| deps.api.addr_humanize(&addr).map(|addr| addr.to_string()))
+            .transpose()?,
     })
 }
 
@@ -278,13 +331,13 @@ fn calculate_decimal_rewards(
 }
 
 // calculate the reward with decimal
-fn get_decimals(value: Decimal) -> StdResult<Decimal> {
-    let stringed: &str = &*value.to_string();
-    let parts: &[&str] = &*stringed.split('.').collect::<Vec<&str>>();
+pub(crate) fn get_decimals(value: Decimal) -> StdResult<Decimal> {
+    let stringed: &str = &value.to_string();
+    let parts: &[&str] = &stringed.split('.').collect::<Vec<&str>>();
     match parts.len() {
         1 => Ok(Decimal::zero()),
         2 => {
-            let decimals = Decimal::from_str(&*("0.".to_owned() + parts[1]))?;
+            let decimals = Decimal::from_str(&("0.".to_owned() + parts[1]))?;
             Ok(decimals)
         }
         _ => Err(StdError::generic_err("Unexpected number of dots")),
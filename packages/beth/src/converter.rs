@@ -1,6 +1,8 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
+use cosmwasm_std::{Binary, Uint128};
+use cw0::Duration;
 use cw20::Cw20ReceiveMsg;
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -20,21 +22,248 @@ pub enum ExecuteMsg {
         wormhole_token_address: String,
         anchor_token_address: String,
     },
+
+    ////////////////////
+    /// Owner's operations
+    ///////////////////
+
+    /// Propose a new owner, who has to accept the ownership before it expires
+    ProposeNewOwner {
+        new_owner: String,
+        expires_in: Option<u64>,
+    },
+    /// Cancel the pending ownership transfer
+    CancelOwnershipTransfer {},
+
+    ////////////////////
+    /// Pending owner's operations
+    ///////////////////
+
+    /// Accept the proposed ownership transfer
+    AcceptOwnership {},
+
+    ////////////////////
+    /// Owner's source token operations
+    ///////////////////
+
+    /// Whitelist an additional bridged token as a conversion source
+    AddSourceToken { token_address: String },
+    /// Enable or disable new deposits of a source token
+    UpdateSourceToken {
+        token_address: String,
+        enabled: bool,
+    },
+    /// Remove a source token which does not back any anchor token
+    RemoveSourceToken { token_address: String },
+    /// Whitelist a native denom as a conversion source
+    AddNativeSource { denom: String, decimals: u8 },
+    /// Enable or disable new deposits of a native source
+    UpdateNativeSource { denom: String, enabled: bool },
+    /// Remove a native source which does not back any anchor token
+    RemoveNativeSource { denom: String },
+
+    /// Update the conversion fees, in basis points, and the fee collector
+    UpdateFees {
+        fee_collector: Option<String>,
+        to_anchor_fee_bps: Option<u64>,
+        to_wormhole_fee_bps: Option<u64>,
+    },
+    /// Set or clear the guardian address, which is allowed to pause conversions
+    SetGuardian { guardian: Option<String> },
+    /// Unpause minting and/or redemption, only the owner can unpause
+    Unpause { mint: bool, redeem: bool },
+    /// Add addresses to and remove them from the allowlist
+    UpdateAllowlist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Add addresses to and remove them from the denylist, which can never convert
+    UpdateDenylist {
+        add: Vec<String>,
+        remove: Vec<String>,
+    },
+    /// Restrict conversions to allowlisted senders
+    SetAllowlistOnly { allowlist_only: bool },
+    /// Cap the anchor token minted and redeemed per window of blocks or seconds
+    UpdateRateLimit {
+        window: Duration,
+        mint_limit: Option<Uint128>,
+        redeem_limit: Option<Uint128>,
+    },
+    /// Send the wormhole token held above the outstanding anchor token supply
+    SweepSurplus { recipient: String },
+    /// Send the whole balance of a cw20 token which is not a conversion source,
+    /// e.g. sent to the converter by accident
+    RecoverToken {
+        token_address: String,
+        recipient: String,
+    },
+
+    ////////////////////
+    /// User's operations
+    ///////////////////
+
+    /// Mint anchor token for the whitelisted native coin sent along,
+    /// to the recipient or forwarded to a contract
+    ConvertNativeToAnchor {
+        recipient: Option<String>,
+        forward: Option<Forward>,
+    },
+    /// Convert tokens drawn from the allowance the sender granted to the converter
+    ConvertFrom {
+        direction: ConvertDirection,
+        amount: Uint128,
+        recipient: Option<String>,
+    },
+
+    ////////////////////
+    /// Owner's token migration operations
+    ///////////////////
+
+    /// Propose to replace the registered wormhole token with a new token,
+    /// the legacy backing is swapped by the migration contract once the timelock expires
+    ProposeTokenMigration {
+        new_token_address: String,
+        migration_contract: String,
+        migration_msg: Binary,
+    },
+    /// Cancel the pending token migration
+    CancelTokenMigration {},
+    /// Send the legacy backing to the migration contract and switch to the new token
+    ExecuteTokenMigration {},
+
+    ////////////////////
+    /// Owner's timelock operations
+    ///////////////////
+
+    /// Queue an owner operation, which anyone can execute once the timelock delay has passed
+    ProposeAction { msg: Box<ExecuteMsg> },
+    /// Cancel a queued operation
+    CancelAction { id: u64 },
+    /// Set the timelock delay in seconds, owner operations have to be queued once it is set
+    SetTimelockDelay { delay: u64 },
+
+    ////////////////////
+    /// User's timelock operations
+    ///////////////////
+
+    /// Execute a queued operation after its timelock has expired
+    ExecuteAction { id: u64 },
+
+    ////////////////////
+    /// Internal operations
+    ///////////////////
+
+    /// Check the new token has been received and register it as the wormhole token
+    FinalizeTokenMigration {},
+
+    ////////////////////
+    /// Owner's and guardian's operations
+    ///////////////////
+
+    /// Pause minting and/or redemption
+    Pause { mint: bool, redeem: bool },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum Cw20HookMsg {
-    // convert wormhole token to anchor token
-    ConvertWormholeToAnchor {},
-    // convert anchor token to wormhole token
-    ConvertAnchorToWormhole {},
+    // convert any registered source token to anchor token,
+    // minted to the recipient or forwarded to a contract
+    ConvertWormholeToAnchor {
+        recipient: Option<String>,
+        forward: Option<Forward>,
+    },
+    // convert anchor token to the given source token,
+    // or to the registered wormhole token if none is given
+    ConvertAnchorToWormhole {
+        source_token: Option<String>,
+        recipient: Option<String>,
+        forward: Option<Forward>,
+    },
+    // convert anchor token to the given native denom
+    ConvertAnchorToNative {
+        denom: String,
+        recipient: Option<String>,
+        forward: Option<Forward>,
+    },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum ConvertDirection {
+    /// transfer the given source token, or the registered wormhole token if none is given,
+    /// and mint anchor token
+    ToAnchor { source_token: Option<String> },
+    /// burn anchor token and return the given source token,
+    /// or the registered wormhole token if none is given
+    ToWormhole { source_token: Option<String> },
+}
+
+/// Contract receiving the converted tokens along with a hook message,
+/// through a cw20 Send or an execution with the native coins attached
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct Forward {
+    pub contract: String,
+    pub msg: Binary,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum QueryMsg {
     Config {},
+    SourceToken {
+        token_address: String,
+    },
+    SourceTokens {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    NativeSource {
+        denom: String,
+    },
+    NativeSources {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    PauseStatus {},
+    RateLimit {},
+    PendingOwner {},
+    /// Source token balances of the converter against the anchor token supply
+    Backing {},
+    /// Status of the proposed or last completed token migration
+    TokenMigration {},
+    /// Cumulative conversion totals since the statistics were introduced
+    Stats {},
+    /// Owner operation queued under the given id
+    QueuedAction {
+        id: u64,
+    },
+    /// Owner operations queued and not executed nor cancelled yet
+    QueuedActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
+    Allowlist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    Denylist {
+        start_after: Option<String>,
+        limit: Option<u32>,
+    },
+    /// Simulate a conversion of the given source token or native denom, or of the
+    /// registered wormhole token if none is given, to anchor token
+    SimulateConvertToAnchor {
+        source_token: Option<String>,
+        amount: Uint128,
+    },
+    /// Simulate a conversion of anchor token to the given source token or native denom,
+    /// or to the registered wormhole token if none is given
+    SimulateConvertToWormhole {
+        source_token: Option<String>,
+        amount: Uint128,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -42,8 +271,169 @@ pub struct ConfigResponse {
     pub owner: String,
     pub wormhole_token_address: Option<String>,
     pub anchor_token_address: Option<String>,
+    pub wormhole_decimals: Option<u8>,
+    pub anchor_decimals: Option<u8>,
+    pub fee_collector: Option<String>,
+    pub to_anchor_fee_bps: u64,
+    pub to_wormhole_fee_bps: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<String>,
+    /// time in seconds after which the proposal can no longer be accepted
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SourceTokenResponse {
+    pub token_address: String,
+    pub decimals: u8,
+    pub enabled: bool,
+    pub backing: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SourceTokensResponse {
+    pub source_tokens: Vec<SourceTokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeSourceResponse {
+    pub denom: String,
+    pub decimals: u8,
+    pub enabled: bool,
+    pub backing: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct NativeSourcesResponse {
+    pub native_sources: Vec<NativeSourceResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PauseStatusResponse {
+    pub guardian: Option<String>,
+    pub mint_paused: bool,
+    pub redeem_paused: bool,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RateLimitResponse {
+    pub window: Option<Duration>,
+    /// block height or time of the oldest conversion counted in the window
+    pub window_start: Option<u64>,
+    pub mint_limit: Option<Uint128>,
+    pub redeem_limit: Option<Uint128>,
+    pub minted: Uint128,
+    pub redeemed: Uint128,
+    pub remaining_mint: Option<Uint128>,
+    pub remaining_redeem: Option<Uint128>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SimulationResponse {
+    /// amount minted or returned to the recipient
+    pub return_amount: Uint128,
+    /// amount of the input token sent back because it cannot be converted
+    pub refund_amount: Uint128,
+    /// amount of the input token charged as fee
+    pub fee_amount: Uint128,
+    /// smallest input amount which can be converted
+    pub min_amount: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedActionResponse {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    /// time in seconds from which the operation can be executed
+    pub eta: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedActionsResponse {
+    /// timelock delay in seconds
+    pub delay: u64,
+    pub actions: Vec<QueuedActionResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AllowlistResponse {
+    /// whether only allowlisted senders can convert
+    pub allowlist_only: bool,
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DenylistResponse {
+    pub addresses: Vec<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct StatsResponse {
+    pub total_minted: Uint128,
+    pub total_burned: Uint128,
+    pub to_anchor_conversions: u64,
+    pub to_wormhole_conversions: u64,
+    /// totals of every source that has been converted, in its own decimals
+    pub sources: Vec<SourceStatsResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct SourceStatsResponse {
+    /// source token address or native denom
+    pub source: String,
+    /// deposited amount, including fees
+    pub total_deposited: Uint128,
+    pub total_returned: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct BackingResponse {
+    /// decimals both amounts are normalized to
+    pub decimals: u8,
+    /// source token balances held by the converter
+    pub wormhole_balance: Uint128,
+    /// total supply of the anchor token
+    pub anchor_supply: Uint128,
+    pub is_backed: bool,
+    /// balance exceeding the supply, zero if not backed
+    pub surplus: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 #[serde(rename_all = "snake_case")]
-pub struct MigrateMsg {}
+pub enum TokenMigrationStatus {
+    Pending,
+    Completed,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct TokenMigrationResponse {
+    pub legacy_token_address: String,
+    pub new_token_address: String,
+    pub migration_contract: String,
+    pub migration_msg: Binary,
+    pub eta: u64,
+    pub migrated_amount: Uint128,
+    pub status: TokenMigrationStatus,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub struct MigrateMsg {
+    /// parameters of the state migrations run by this upgrade
+    #[serde(default)]
+    pub params: Vec<MigrationParams>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MigrationParams {
+    /// migration of unversioned instances to 0.1.0
+    V010 {
+        /// backing recorded for the wormhole token, defaults to the converter balance
+        wormhole_backing: Option<Uint128>,
+    },
+}
@@ -1,12 +1,13 @@
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
 
-use cosmwasm_std::{Decimal, Uint128};
+use cosmwasm_std::{Coin, Decimal, Uint128};
+use cw20::{Cw20Coin, Cw20ReceiveMsg};
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct InstantiateMsg {
     pub owner: String,
-    pub reward_denom: String,
+    pub reward_denoms: Vec<String>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -18,9 +19,41 @@ pub enum ExecuteMsg {
 
     /// Set the token contract addess after initialization
     PostInitialize { token_contract: String },
+    /// Start distributing an additional native denom
+    AddRewardDenom { denom: String },
+    /// Accept an additional cw20 token as reward
+    AddRewardToken { token_address: String },
+    /// Send the coins held which are not owed to the holders
+    RecoverUnaccounted { recipient: String },
+    /// Set the seconds over which new deposits are released to the holders
+    UpdateDistributionPeriod { distribution_period: u64 },
+    /// Set the native denoms which are never swapped and the balance worth swapping
+    UpdateSwapConfig {
+        skip_denoms: Option<Vec<String>>,
+        dust_threshold: Option<Uint128>,
+    },
+
+    /// Propose a new owner, who has to accept the ownership before it expires
+    ProposeNewOwner {
+        new_owner: String,
+        expires_in: Option<u64>,
+    },
+    /// Cancel the pending ownership transfer
+    CancelOwnershipTransfer {},
+
+    /// Queue an owner operation, which anyone can execute once the timelock delay has passed
+    ProposeAction { msg: Box<ExecuteMsg> },
+    /// Cancel a queued operation
+    CancelAction { id: u64 },
+    /// Set the timelock delay in seconds, owner operations have to be queued once it is set
+    SetTimelockDelay { delay: u64 },
 
-    /// Update contract configuration, currently only owner is updatable
-    UpdateConfig { owner: String },
+    ////////////////////
+    /// Pending owner's operations
+    ///////////////////
+
+    /// Accept the proposed ownership transfer
+    AcceptOwnership {},
 
     ////////////////////
     /// bAsset's operations
@@ -35,12 +68,46 @@ pub enum ExecuteMsg {
     /// Set current reward index to global index
     DecreaseBalance { address: String, amount: Uint128 },
 
+    ////////////////////
+    /// Reward token's operations
+    ///////////////////
+
+    /// Receive interface for send token.
+    /// Distribute the cw20 reward token sent to the holders
+    Receive(Cw20ReceiveMsg),
+
     ////////////////////
     /// User's operations
     ///////////////////
 
-    /// return the accrued reward in uusd to the user.
+    /// Distribute the reward coins sent along to the holders
+    DepositRewards {},
+    /// Swap the native balances which are not rewards to the first reward denom
+    SwapToRewardDenom {},
+    /// return the accrued rewards of every reward denom to the user.
     ClaimRewards { recipient: Option<String> },
+    /// Set where the sender's rewards go when claimed without a recipient
+    SetWithdrawAddress { address: String },
+    /// Execute a queued operation after its timelock has expired
+    ExecuteAction { id: u64 },
+
+    ////////////////////
+    /// Internal operations
+    ///////////////////
+
+    /// Callback of SwapToRewardDenom, rejected unless sent by the contract itself.
+    /// Credits the reward denom balance above `prev_reward_balance`, recorded before the swaps
+    UpdateGlobalIndex { prev_reward_balance: Uint128 },
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct MigrateMsg {}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Cw20HookMsg {
+    // distribute the tokens sent to the current holders
+    DepositRewards {},
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
@@ -51,6 +118,14 @@ pub enum QueryMsg {
     AccruedRewards {
         address: String,
     },
+    /// cw20 rewards accrued by the holder
+    AccruedTokenRewards {
+        address: String,
+    },
+    /// cw20 tokens accepted as rewards
+    RewardTokens {},
+    /// Emission rate and undistributed rewards of each reward
+    Distribution {},
     Holder {
         address: String,
     },
@@ -58,31 +133,123 @@ pub enum QueryMsg {
         start_after: Option<String>,
         limit: Option<u32>,
     },
+    PendingOwner {},
+    /// Owner operation queued under the given id
+    QueuedAction {
+        id: u64,
+    },
+    /// Owner operations queued and not executed nor cancelled yet
+    QueuedActions {
+        start_after: Option<u64>,
+        limit: Option<u32>,
+    },
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct ConfigResponse {
     pub owner: String,
-    pub reward_denom: String,
+    pub reward_denoms: Vec<String>,
+    pub reward_tokens: Vec<String>,
     pub token_contract: Option<String>,
+    /// seconds over which each deposit is released to the holders
+    pub distribution_period: u64,
+    pub swap_skip_denoms: Vec<String>,
+    pub swap_dust_threshold: Uint128,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct PendingOwnerResponse {
+    pub pending_owner: Option<String>,
+    /// time in seconds after which the proposal can no longer be accepted
+    pub expires_at: Option<u64>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedActionResponse {
+    pub id: u64,
+    pub msg: ExecuteMsg,
+    /// time in seconds from which the operation can be executed
+    pub eta: u64,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct QueuedActionsResponse {
+    /// timelock delay in seconds
+    pub delay: u64,
+    pub actions: Vec<QueuedActionResponse>,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct StateResponse {
-    pub global_index: Decimal,
     pub total_balance: Uint128,
+    pub reward_indices: Vec<RewardIndexResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardIndexResponse {
+    pub denom: String,
+    pub global_index: Decimal,
     pub prev_reward_balance: Uint128,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct AccruedRewardsResponse {
-    pub rewards: Uint128,
+    pub rewards: Vec<Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct AccruedTokenRewardsResponse {
+    pub rewards: Vec<Cw20Coin>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardTokenResponse {
+    pub token_address: String,
+    pub global_index: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardTokensResponse {
+    pub reward_tokens: Vec<RewardTokenResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct DistributionResponse {
+    pub distribution_period: u64,
+    pub streams: Vec<RewardStreamResponse>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct RewardStreamResponse {
+    /// reward denom or cw20 token address
+    pub reward: String,
+    /// rewards currently released per second, over all the deposits streamed
+    pub emission_rate: Decimal,
+    /// rewards deposited and not released yet
+    pub undistributed: Uint128,
+    /// time in seconds at which everything has been released
+    pub end: u64,
 }
 
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
 pub struct HolderResponse {
     pub address: String,
     pub balance: Uint128,
+    pub reward_indices: Vec<HolderIndexResponse>,
+    pub token_indices: Vec<HolderTokenIndexResponse>,
+    pub withdraw_address: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderIndexResponse {
+    pub denom: String,
+    pub index: Decimal,
+    pub pending_rewards: Decimal,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq, JsonSchema)]
+pub struct HolderTokenIndexResponse {
+    pub token_address: String,
     pub index: Decimal,
     pub pending_rewards: Decimal,
 }